@@ -2,18 +2,64 @@
 
 #[ink::contract]
 mod interactor {
-    use erc20::erc20::{Erc20Ref, Result as Erc20Result};
+    use erc20::erc20::{Erc20Ref, Error as Erc20Error, Result as Erc20Result};
     use ink::U256;
 
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum ProxyError {
+        InsufficientBalance,
+        InsufficientAllowance,
+        CrossContractFailed,
+        NotOwner,
+        Token(Erc20Error),
+        Overflow,
+    }
+
+    impl From<Erc20Error> for ProxyError {
+        fn from(error: Erc20Error) -> Self {
+            match error {
+                Erc20Error::InsufficientBalance => ProxyError::InsufficientBalance,
+                Erc20Error::InsufficientAllowance => ProxyError::InsufficientAllowance,
+                other => ProxyError::Token(other),
+            }
+        }
+    }
+
+    /// Maps the outcome of a token call into a `ProxyError`, folding dispatch failures into `CrossContractFailed`.
+    pub fn proxy_result<E>(result: Result<ink::MessageResult<Erc20Result<()>>, E>) -> Result<bool, ProxyError> {
+        match result {
+            Ok(Ok(Ok(()))) => Ok(true),
+            Ok(Ok(Err(error))) => Err(error.into()),
+            _ => Err(ProxyError::CrossContractFailed),
+        }
+    }
+
+    /// Converts `whole_units` into base units for a token with `decimals` places.
+    pub fn to_base_units(whole_units: u128, decimals: u8) -> Option<U256> {
+        U256::from(10u8).checked_pow(U256::from(decimals))?.checked_mul(U256::from(whole_units))
+    }
+
     #[ink(storage)]
     pub struct Caller {
         token: Erc20Ref,
+        owner: Address,
     }
 
     impl Caller {
         #[ink(constructor)]
         pub fn new(token: Erc20Ref) -> Self {
-            Self { token }
+            Self { token, owner: Self::env().caller() }
+        }
+
+        #[ink(message)]
+        pub fn owner(&self) -> Address { self.owner }
+
+        #[ink(message)]
+        pub fn transfer_proxy_ownership(&mut self, new_owner: Address) -> Result<(), ProxyError> {
+            self.ensure_owner()?;
+            self.owner = new_owner;
+            Ok(())
         }
 
         #[ink(message)]
@@ -23,30 +69,69 @@ mod interactor {
         pub fn token_balance_of(&self, owner: Address) -> U256 { self.token.balance_of(owner) }
 
         #[ink(message)]
-        pub fn token_transfer(&mut self, to: Address, value: U256) -> Erc20Result<()> { self.token.transfer(to, value) }
+        pub fn token_transfer(&mut self, to: Address, value: U256) -> Result<(), ProxyError> {
+            self.ensure_owner()?;
+            self.token.transfer(to, value).map_err(ProxyError::from)
+        }
 
+        /// Transfers `whole_units` of the token, scaled by its `decimals`.
         #[ink(message)]
-        pub fn token_approve(&mut self, spender: Address, value: U256) -> Erc20Result<()> { self.token.approve(spender, value) }
+        pub fn token_transfer_normalized(&mut self, to: Address, whole_units: u128) -> Result<(), ProxyError> {
+            self.ensure_owner()?;
+            let value = to_base_units(whole_units, self.token.decimals()).ok_or(ProxyError::Overflow)?;
+            self.token.transfer(to, value).map_err(ProxyError::from)
+        }
 
         #[ink(message)]
-        pub fn token_transfer_from(&mut self, from: Address, to: Address, value: U256) -> Erc20Result<()> { self.token.transfer_from(from, to, value) }
+        pub fn token_transfer_checked(&mut self, to: Address, value: U256) -> Result<bool, ProxyError> {
+            self.ensure_owner()?;
+            proxy_result(self.token.call_mut().transfer(to, value).try_invoke())
+        }
+
+        #[ink(message)]
+        pub fn token_approve(&mut self, spender: Address, value: U256) -> Result<(), ProxyError> {
+            self.ensure_owner()?;
+            self.token.approve(spender, value).map_err(ProxyError::from)
+        }
+
+        #[ink(message)]
+        pub fn token_transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<(), ProxyError> {
+            self.ensure_owner()?;
+            self.token.transfer_from(from, to, value).map_err(ProxyError::from)
+        }
 
         #[ink(message)]
         pub fn token_allowance(&self, owner: Address, spender: Address) -> U256 { self.token.allowance(owner, spender) }
 
+        /// Spends an allowance granted to this contract, moving `value` from `owner` to `to`.
+        #[ink(message)]
+        pub fn token_spend_allowance(&mut self, owner: Address, to: Address, value: U256) -> Result<(), ProxyError> {
+            self.ensure_owner()?;
+            self.token.transfer_from(owner, to, value).map_err(ProxyError::from)
+        }
+
+        #[ink(message)]
+        pub fn token_remaining_allowance(&self, owner: Address) -> U256 { self.token.allowance(owner, self.env().address()) }
+
         #[ink(message)]
         pub fn get_token_address(&self) -> Address { 
             // Note: In ink! v6, we can't directly get the account_id from Erc20Ref
             // This would need to be stored separately or handled differently
             Address::from([0u8; 20])
         }
+
+        fn ensure_owner(&self) -> Result<(), ProxyError> {
+            if self.env().caller() != self.owner { return Err(ProxyError::NotOwner) }
+            Ok(())
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::interactor::Caller;
-    use ink::U256;
+    use super::interactor::{proxy_result, to_base_units, Caller, ProxyError};
+    use erc20::erc20::{Erc20Ref, Error as Erc20Error};
+    use ink::{env::{call::FromAddr, test}, primitives::Address, U256};
 
     #[ink::test]
     fn constructor_works() {
@@ -62,4 +147,133 @@ mod tests {
         // Note: This requires a deployed ERC20 contract
         assert!(true);
     }
-}
\ No newline at end of file
+
+    #[ink::test]
+    fn proxy_result_maps_token_errors() {
+        assert_eq!(proxy_result::<()>(Ok(Ok(Ok(())))), Ok(true));
+        assert_eq!(proxy_result::<()>(Ok(Ok(Err(Erc20Error::InsufficientBalance)))), Err(ProxyError::InsufficientBalance));
+        assert_eq!(proxy_result::<()>(Ok(Ok(Err(Erc20Error::InsufficientAllowance)))), Err(ProxyError::InsufficientAllowance));
+        assert_eq!(proxy_result::<()>(Ok(Ok(Err(Erc20Error::NotOwner)))), Err(ProxyError::Token(Erc20Error::NotOwner)));
+    }
+
+    #[ink::test]
+    fn proxy_result_maps_dispatch_failures() {
+        assert_eq!(proxy_result::<()>(Ok(Err(ink::LangError::CouldNotReadInput))), Err(ProxyError::CrossContractFailed));
+        assert_eq!(proxy_result(Err(())), Err(ProxyError::CrossContractFailed));
+    }
+
+    #[ink::test]
+    fn to_base_units_scales_by_decimals() {
+        assert_eq!(to_base_units(5, 6), Some(U256::from(5_000_000u64)));
+        assert_eq!(to_base_units(5, 0), Some(U256::from(5u8)));
+        assert_eq!(to_base_units(1, 255), None);
+    }
+
+    #[ink::test]
+    fn passthroughs_reject_non_owner() {
+        let owner = Address::from([5u8; 20]);
+        let stranger = Address::from([6u8; 20]);
+        test::set_caller(owner);
+        let mut caller = Caller::new(Erc20Ref::from_addr(Address::from([9u8; 20])));
+        assert_eq!(caller.owner(), owner);
+
+        test::set_caller(stranger);
+        assert_eq!(caller.token_transfer(stranger, U256::from(1u8)), Err(ProxyError::NotOwner));
+        assert_eq!(caller.token_approve(stranger, U256::from(1u8)), Err(ProxyError::NotOwner));
+        assert_eq!(caller.token_transfer_from(owner, stranger, U256::from(1u8)), Err(ProxyError::NotOwner));
+        assert_eq!(caller.transfer_proxy_ownership(stranger), Err(ProxyError::NotOwner));
+    }
+
+    #[ink::test]
+    fn proxy_ownership_can_be_transferred() {
+        let owner = Address::from([5u8; 20]);
+        let successor = Address::from([6u8; 20]);
+        test::set_caller(owner);
+        let mut caller = Caller::new(Erc20Ref::from_addr(Address::from([9u8; 20])));
+        caller.transfer_proxy_ownership(successor).unwrap();
+        assert_eq!(caller.owner(), successor);
+        assert_eq!(caller.token_transfer(owner, U256::from(1u8)), Err(ProxyError::NotOwner));
+    }
+}
+
+#[cfg(all(test, feature = "e2e-tests"))]
+mod e2e_tests {
+    use super::interactor::{Caller, CallerRef, ProxyError};
+    use erc20::erc20::{Erc20, Erc20Ref};
+    use ink::{ env::call::FromAddr, primitives::Address, U256 };
+    use ink_e2e::ContractsBackend;
+
+    type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[ink_e2e::test]
+    async fn spend_allowance_through_caller_works<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+        let mut erc20_constructor = Erc20Ref::new(U256::from(1000));
+        let erc20 = client
+            .instantiate("erc20", &ink_e2e::alice(), &mut erc20_constructor)
+            .submit()
+            .await
+            .expect("erc20 instantiate failed");
+        let mut erc20_call = erc20.call_builder::<Erc20>();
+
+        let mut caller_constructor = CallerRef::new(Erc20Ref::from_addr(erc20.addr));
+        let caller = client
+            .instantiate("caller", &ink_e2e::alice(), &mut caller_constructor)
+            .submit()
+            .await
+            .expect("caller instantiate failed");
+        let mut caller_call = caller.call_builder::<Caller>();
+
+        // The deployer holds the whole supply and is recorded as the token owner.
+        let alice = client.call(&ink_e2e::alice(), &erc20_call.owner()).dry_run().await?.return_value();
+        let recipient = Address::from([7u8; 20]);
+
+        let approve = erc20_call.approve(caller.addr, U256::from(200));
+        client.call(&ink_e2e::alice(), &approve).submit().await.expect("approve failed");
+        let remaining = client.call(&ink_e2e::alice(), &caller_call.token_remaining_allowance(alice)).dry_run().await?.return_value();
+        assert_eq!(remaining, U256::from(200));
+
+        let spend = caller_call.token_spend_allowance(alice, recipient, U256::from(150));
+        let rejected = client.call(&ink_e2e::bob(), &spend).dry_run().await?.return_value();
+        assert_eq!(rejected, Err(ProxyError::NotOwner));
+        let result = client.call(&ink_e2e::alice(), &spend).submit().await.expect("spend failed");
+        assert_eq!(result.return_value(), Ok(()));
+
+        let balance = client.call(&ink_e2e::alice(), &erc20_call.balance_of(recipient)).dry_run().await?.return_value();
+        assert_eq!(balance, U256::from(150));
+        let remaining = client.call(&ink_e2e::alice(), &caller_call.token_remaining_allowance(alice)).dry_run().await?.return_value();
+        assert_eq!(remaining, U256::from(50));
+        Ok(())
+    }
+
+    #[ink_e2e::test]
+    async fn normalized_transfer_scales_by_token_decimals<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+        let mut erc20_constructor = Erc20Ref::with_metadata(U256::from(100_000_000u64), String::from("USD Coin"), String::from("USDC"), 6);
+        let erc20 = client
+            .instantiate("erc20", &ink_e2e::alice(), &mut erc20_constructor)
+            .submit()
+            .await
+            .expect("erc20 instantiate failed");
+        let mut erc20_call = erc20.call_builder::<Erc20>();
+
+        let mut caller_constructor = CallerRef::new(Erc20Ref::from_addr(erc20.addr));
+        let caller = client
+            .instantiate("caller", &ink_e2e::alice(), &mut caller_constructor)
+            .submit()
+            .await
+            .expect("caller instantiate failed");
+        let mut caller_call = caller.call_builder::<Caller>();
+
+        // The caller contract sends from its own balance, so fund it first.
+        let fund = erc20_call.transfer(caller.addr, U256::from(10_000_000u64));
+        client.call(&ink_e2e::alice(), &fund).submit().await.expect("fund failed");
+
+        let recipient = Address::from([7u8; 20]);
+        let transfer = caller_call.token_transfer_normalized(recipient, 5);
+        let result = client.call(&ink_e2e::alice(), &transfer).submit().await.expect("normalized transfer failed");
+        assert_eq!(result.return_value(), Ok(()));
+
+        let balance = client.call(&ink_e2e::alice(), &erc20_call.balance_of(recipient)).dry_run().await?.return_value();
+        assert_eq!(balance, U256::from(5_000_000u64));
+        Ok(())
+    }
+}
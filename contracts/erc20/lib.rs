@@ -4,14 +4,152 @@
 mod test;
 #[ink::contract]
 pub mod erc20 {
-    use ink::{ U256, storage::Mapping };
+    use ink::{ U256, env::hash::Keccak256, storage::{ Mapping, StorageVec } };
+    use ink::env::call::{ build_call, ExecutionInput, Selector };
+    use ink::prelude::{ format, string::{ String, ToString }, vec, vec::Vec };
+
+    pub const VERSION: u32 = 2;
+    pub const MAX_BPS: u16 = 10_000;
+    pub const DEFAULT_DECIMALS: u8 = 18;
+    /// Resources forwarded to best-effort callbacks, so a callee that burns its budget fails alone.
+    pub const CALLBACK_REF_TIME_LIMIT: u64 = 5_000_000_000;
+    pub const CALLBACK_PROOF_SIZE_LIMIT: u64 = 256 * 1024;
+    pub const CALLBACK_STORAGE_DEPOSIT_LIMIT: u128 = 1_000_000_000_000;
+    /// Fixed-point precision of `redemption_rate`, i.e. a rate of `RATE_PRECISION` pays one native unit per token unit.
+    pub const RATE_PRECISION: u128 = 1_000_000_000_000_000_000;
+    pub const RECENT_TRANSFERS_LIMIT: usize = 10;
+    pub const MAX_REFERRAL_BPS: u16 = 500;
+    pub const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+    pub const SECONDS_PER_YEAR: u64 = 365 * SECONDS_PER_DAY;
+    pub const EIP712_VERSION: &str = "1";
+    /// Codes returned by `restrictions_for`.
+    pub const RESTRICTION_PAUSED: u8 = 1;
+    pub const RESTRICTION_BLACKLISTED: u8 = 2;
+    pub const RESTRICTION_FROZEN: u8 = 3;
+    pub const RESTRICTION_HOLD_PERIOD: u8 = 4;
+    pub const RESTRICTION_NOT_WHITELISTED: u8 = 5;
+    pub const RESTRICTION_BLOCK_LIMIT: u8 = 6;
+    const EIP712_DOMAIN_TYPE: &[u8] = b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+    const TRANSFER_WITH_AUTHORIZATION_TYPE: &[u8] =
+        b"TransferWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)";
+    const CANCEL_AUTHORIZATION_TYPE: &[u8] = b"CancelAuthorization(address authorizer,bytes32 nonce)";
 
     #[ink(storage)]
     #[derive(Default)]
     pub struct Erc20 {
         total_supply: U256,
+        name: String,
+        symbol: String,
+        decimals: u8,
         balances: Mapping<Address, U256>,
         allowances: Mapping<(Address, Address), U256>,
+        owner: Address,
+        fee_bps: u16,
+        fee_collector: Address,
+        fee_exempt: Mapping<Address, bool>,
+        fee_holiday_start: u64,
+        fee_holiday_end: u64,
+        indexed_transfers: bool,
+        open_fills: Mapping<(Address, Address, u64), U256>,
+        max_transfers_per_block: u32,
+        block_transfers: Mapping<Address, (u32, u32)>,
+        airdrop_allocations: Mapping<Address, U256>,
+        airdrop_budget: U256,
+        airdrop_claimed: U256,
+        airdrop_swept: bool,
+        claim_deadline: u64,
+        chain_id: u64,
+        scale_numerator: U256,
+        scale_denominator: U256,
+        balance_scales: Mapping<Address, (U256, U256)>,
+        redemption_rate: U256,
+        native_deposits: Mapping<Address, U256>,
+        wrapped_supply: U256,
+        last_transfer_at: Mapping<Address, u64>,
+        authorization_state: Mapping<(Address, [u8; 32]), bool>,
+        approved_spenders: Mapping<Address, Vec<Address>>,
+        total_allowance_granted: Mapping<Address, U256>,
+        transfers_paused: bool,
+        approvals_paused: bool,
+        mints_paused: bool,
+        tax_sink: Option<Address>,
+        config_locked: bool,
+        total_burned: U256,
+        stakes: Mapping<Address, Stake>,
+        total_staked: U256,
+        reward_rate_per_sec: U256,
+        blocked_code_hashes: Mapping<[u8; 32], bool>,
+        registered_receivers: Mapping<Address, bool>,
+        aliases: Mapping<[u8; 32], Address>,
+        recovery_delay: u64,
+        pending_recovery: Option<PendingRecovery>,
+        reflection_bps: u16,
+        reflection_per_token: U256,
+        reflection_snapshots: Mapping<Address, U256>,
+        pending_links: Mapping<Address, Address>,
+        main_accounts: Mapping<Address, Address>,
+        subaccounts: Mapping<Address, Vec<Address>>,
+        approval_epoch: u64,
+        allowance_epochs: Mapping<(Address, Address), u64>,
+        cap_allowance_to_balance: bool,
+        holder_list: StorageVec<Address>,
+        is_holder: Mapping<Address, bool>,
+        holder_count: u32,
+        fee_start_bps: u16,
+        fee_end_bps: u16,
+        fee_start_time: u64,
+        fee_end_time: u64,
+        breaker_threshold_bps: u16,
+        minters: Mapping<Address, bool>,
+        approval_cooldown: u64,
+        approved_at: Mapping<(Address, Address), u64>,
+        allowance_expiries: Mapping<(Address, Address), u64>,
+        first_seen: Mapping<Address, u64>,
+        loyalty_bps_per_day: u32,
+        loyalty_cap_bps: u32,
+        fee_split: Option<(Address, Address, u16)>,
+        whitelist_mode: bool,
+        whitelisted_until: Mapping<Address, u64>,
+        account_frozen: Mapping<Address, bool>,
+        reflection_excluded: Vec<Address>,
+        treasury: Address,
+        fee_tiers: Vec<(U256, u16)>,
+        transfer_seq: u64,
+        spend_policies: Mapping<(Address, Address), SpendPolicy>,
+        policy_spent: Mapping<(Address, Address), U256>,
+        recent_transfers: Mapping<Address, Vec<(Address, U256, u64)>>,
+        liquidity_bps: u16,
+        liquidity_pool: Option<Address>,
+        pending_liquidity: U256,
+        single_use: Mapping<(Address, Address), bool>,
+        delegates: Mapping<Address, Address>,
+        voting_weight: Mapping<Address, U256>,
+        vote_checkpoints: Mapping<Address, Vec<(u32, U256)>>,
+        min_hold_secs: u64,
+        acquired_at: Mapping<Address, u64>,
+        blacklisted_count: u32,
+        blacklisted: Mapping<Address, bool>,
+        migration_mode: bool,
+        referrers: Mapping<Address, Address>,
+        referral_bps: u16,
+        cap: Option<U256>,
+        mint_proposals: Mapping<u64, MintProposal>,
+        next_mint_proposal: u64,
+        vesting: Mapping<Address, Vec<VestingSchedule>>,
+        total_vesting: U256,
+        commitment_salt: [u8; 32],
+        spend_destinations: Mapping<(Address, Address), Address>,
+        migrated_from: Option<Address>,
+        registry: Option<Address>,
+        spend_rate_limit: Mapping<Address, (U256, u64)>,
+        spend_rate_usage: Mapping<Address, (U256, u64)>,
+        payment_requests: Mapping<(Address, Address), (U256, [u8; 32])>,
+        reject_self_transfers: bool,
+        received_by_contract: U256,
+        last_price: U256,
+        cost_basis: Mapping<Address, U256>,
+        holder_fee_tiers: Vec<(u64, u16)>,
+        min_transfer: U256,
     }
 
     #[ink(event)]
@@ -23,6 +161,138 @@ pub mod erc20 {
         value: U256,
     }
 
+    /// Emitted alongside `Transfer` when indexed transfers are enabled, with `magnitude` = floor(log10(value)).
+    #[ink(event)]
+    pub struct TransferIndexed {
+        #[ink(topic)]
+        from: Option<Address>,
+        #[ink(topic)]
+        to: Option<Address>,
+        #[ink(topic)]
+        magnitude: u8,
+        value: U256,
+    }
+
+    #[ink(event)]
+    pub struct PaymentCallbackFailed {
+        #[ink(topic)]
+        to: Address,
+        invoice_id: u64,
+    }
+
+    #[ink(event)]
+    pub struct AuthorizationUsed {
+        #[ink(topic)]
+        authorizer: Address,
+        #[ink(topic)]
+        nonce: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct AuthorizationCanceled {
+        #[ink(topic)]
+        authorizer: Address,
+        #[ink(topic)]
+        nonce: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct RecoveryProposed {
+        #[ink(topic)]
+        from: Address,
+        #[ink(topic)]
+        to: Address,
+        value: U256,
+        eta: u64,
+    }
+
+    #[ink(event)]
+    pub struct RecoveryExecuted {
+        #[ink(topic)]
+        from: Address,
+        #[ink(topic)]
+        to: Address,
+        value: U256,
+    }
+
+    #[ink(event)]
+    pub struct PaymentRequested {
+        #[ink(topic)]
+        from: Address,
+        #[ink(topic)]
+        to: Address,
+        value: U256,
+        memo: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct BuybackBurn {
+        amount: U256,
+    }
+
+    #[ink(event)]
+    pub struct Blacklisted {
+        #[ink(topic)]
+        account: Address,
+    }
+
+    #[ink(event)]
+    pub struct Unblacklisted {
+        #[ink(topic)]
+        account: Address,
+    }
+
+    #[ink(event)]
+    pub struct DelegateChanged {
+        #[ink(topic)]
+        delegator: Address,
+        from_delegate: Option<Address>,
+        #[ink(topic)]
+        to_delegate: Address,
+    }
+
+    /// Emitted after every transfer's `Transfer` events; `seq` increases by one per transfer.
+    #[ink(event)]
+    pub struct SequencedTransfer {
+        seq: u64,
+        #[ink(topic)]
+        from: Address,
+        #[ink(topic)]
+        to: Address,
+        value: U256,
+    }
+
+    /// Emitted next to the mint's `Transfer` to record why it happened.
+    #[ink(event)]
+    pub struct ReasonedMint {
+        #[ink(topic)]
+        to: Address,
+        value: U256,
+        reason: String,
+    }
+
+    /// Emitted when a transfer of `value` exceeds the breaker threshold and pauses transfers.
+    #[ink(event)]
+    pub struct CircuitBreakerTripped {
+        #[ink(topic)]
+        from: Address,
+        value: U256,
+    }
+
+    /// Emitted when a transfer skims `value` for redistribution to eligible holders.
+    #[ink(event)]
+    pub struct Reflected {
+        #[ink(topic)]
+        from: Address,
+        value: U256,
+    }
+
+    #[ink(event)]
+    pub struct Redenomination {
+        factor: u32,
+        multiply: bool,
+    }
+
     #[ink(event)]
     pub struct Approval {
         #[ink(topic)]
@@ -34,70 +304,2601 @@ pub mod erc20 {
 
     #[derive(Debug, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
-    pub enum Error { InsufficientBalance, InsufficientAllowance }
+    pub enum Error {
+        InsufficientBalance,
+        InsufficientAllowance,
+        NotOwner,
+        InvalidFee,
+        InvalidWindow,
+        Overfill,
+        TooManyTransfers,
+        AllowanceMismatch,
+        Overflow,
+        NothingToClaim,
+        ClaimWindowClosed,
+        ClaimWindowOpen,
+        InvalidFactor,
+        SlippageExceeded,
+        NativeTransferFailed,
+        ExceedsDeposit,
+        BelowMinTransfer,
+        InvalidSignature,
+        AuthorizationUsed,
+        AuthorizationNotYetValid,
+        AuthorizationExpired,
+        TransfersPaused,
+        ApprovalsPaused,
+        MintsPaused,
+        ConfigLocked,
+        InsufficientStake,
+        EscrowOutstanding,
+        BlockedCodeHash,
+        ReceiverRejected,
+        UnknownAlias,
+        NoPendingRecovery,
+        RecoveryNotReady,
+        NoPendingLink,
+        AlreadyLinked,
+        LengthMismatch,
+        ZeroWeight,
+        NotMinter,
+        ApprovalTooRecent,
+        AllowanceNotStale,
+        NotWhitelisted,
+        ApprovalExceedsBalance,
+        AccountFrozen,
+        TargetAboveSupply,
+        InvalidTiers,
+        PolicyPerTxExceeded,
+        PolicyTotalExceeded,
+        PolicyExpired,
+        NoLiquidityPool,
+        HoldPeriodActive,
+        Blacklisted,
+        MigrationClosed,
+        ReferrerAlreadySet,
+        SelfReferral,
+        DeadlinePassed,
+        CapExceeded,
+        UnknownProposal,
+        AlreadyApproved,
+        ProposalExecuted,
+        DestinationRestricted,
+        NoHolders,
+        SpendRateExceeded,
+        SelfContractTransfer,
+    }
 
     pub type Result<T> = core::result::Result<T, Error>;
 
+    /// Owner recovery waiting for its timelock to pass.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct PendingRecovery {
+        pub from: Address,
+        pub to: Address,
+        pub value: U256,
+        pub eta: u64,
+    }
+
+    /// Tokens escrowed for a beneficiary, vesting linearly from `start` over `duration` with nothing releasable before `start + cliff`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct VestingSchedule {
+        pub total: U256,
+        pub released: U256,
+        pub start: u64,
+        pub duration: u64,
+        pub cliff: u64,
+    }
+
+    /// Mint awaiting a second minter's approval; executes once two distinct minters approve.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct MintProposal {
+        pub to: Address,
+        pub value: U256,
+        pub approvals: Vec<Address>,
+        pub executed: bool,
+    }
+
+    /// Limits on how `transfer_from` may spend an allowance, on top of its amount; zero leaves a limit unset.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct SpendPolicy {
+        pub max_per_tx: U256,
+        pub max_total: U256,
+        pub expires_at: u64,
+    }
+
+    /// Escrowed stake of one account; `accrued` holds rewards settled up to `since`.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Stake {
+        pub amount: U256,
+        pub since: u64,
+        pub accrued: U256,
+    }
+
+    /// Per-account state assembled from the individual getters.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct AccountSnapshot {
+        pub balance: U256,
+        pub fee_exempt: bool,
+        pub airdrop_allocation: U256,
+        pub last_transfer_at: u64,
+        pub frozen: bool,
+        pub blacklisted: bool,
+    }
+
+    /// Snapshot of which optional behaviours are currently active.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct FeatureFlags {
+        pub has_fee: bool,
+        pub fee_holiday: bool,
+        pub indexed_transfers: bool,
+        pub transfers_per_block_capped: bool,
+        pub transfers_paused: bool,
+        pub approvals_paused: bool,
+        pub mints_paused: bool,
+        pub config_locked: bool,
+        pub whitelist_mode: bool,
+        pub blacklist_active: bool,
+        pub has_cap: bool,
+    }
+
+    fn gcd(mut a: U256, mut b: U256) -> U256 {
+        while !b.is_zero() {
+            let r = a % b;
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    /// Returns `bps` basis points of `value`, rounded down, without overflowing on large values.
+    fn bps_of(value: U256, bps: u16) -> U256 {
+        let max = U256::from(MAX_BPS);
+        let bps = U256::from(bps);
+        (value / max) * bps + (value % max) * bps / max
+    }
+
+    fn topic_of<E: ink::env::Event>(name: &str) -> (String, [u8; 32]) { (String::from(name), E::SIGNATURE_TOPIC.unwrap_or_default()) }
+
+    /// Name and signature topic of every event this contract emits, for off-chain decoders.
+    pub fn event_topics() -> Vec<(String, [u8; 32])> {
+        vec![
+            topic_of::<Transfer>("Transfer"),
+            topic_of::<TransferIndexed>("TransferIndexed"),
+            topic_of::<Approval>("Approval"),
+            topic_of::<PaymentCallbackFailed>("PaymentCallbackFailed"),
+            topic_of::<AuthorizationUsed>("AuthorizationUsed"),
+            topic_of::<AuthorizationCanceled>("AuthorizationCanceled"),
+            topic_of::<RecoveryProposed>("RecoveryProposed"),
+            topic_of::<RecoveryExecuted>("RecoveryExecuted"),
+            topic_of::<PaymentRequested>("PaymentRequested"),
+            topic_of::<BuybackBurn>("BuybackBurn"),
+            topic_of::<Blacklisted>("Blacklisted"),
+            topic_of::<Unblacklisted>("Unblacklisted"),
+            topic_of::<DelegateChanged>("DelegateChanged"),
+            topic_of::<SequencedTransfer>("SequencedTransfer"),
+            topic_of::<ReasonedMint>("ReasonedMint"),
+            topic_of::<CircuitBreakerTripped>("CircuitBreakerTripped"),
+            topic_of::<Reflected>("Reflected"),
+            topic_of::<Redenomination>("Redenomination"),
+        ]
+    }
+
+    /// ABI-encodes an address as a left-padded 32-byte word.
+    fn address_word(address: &Address) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[12..].copy_from_slice(&address.0);
+        word
+    }
+
+    fn u64_word(value: u64) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&value.to_be_bytes());
+        word
+    }
+
+    fn u256_word(value: U256) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        for (i, limb) in value.0.iter().enumerate() {
+            word[(3 - i) * 8..(4 - i) * 8].copy_from_slice(&limb.to_be_bytes());
+        }
+        word
+    }
+
+    fn magnitude_of(mut value: U256) -> u8 {
+        let ten = U256::from(10u8);
+        let mut magnitude = 0;
+        while value >= ten {
+            value /= ten;
+            magnitude += 1;
+        }
+        magnitude
+    }
+
     impl Erc20 {
         #[ink(constructor)]
         pub fn new(total_supply: U256) -> Self {
-            let mut balances = Mapping::default();
+            Self::with_metadata(total_supply, String::new(), String::new(), DEFAULT_DECIMALS)
+        }
+
+        #[ink(constructor)]
+        pub fn with_metadata(total_supply: U256, name: String, symbol: String, decimals: u8) -> Self {
             let caller = Self::env().caller();
-            balances.insert(caller, &total_supply);
+            let mut contract = Self {
+                total_supply,
+                name,
+                symbol,
+                decimals,
+                owner: caller,
+                fee_collector: caller,
+                treasury: caller,
+                scale_numerator: U256::one(),
+                scale_denominator: U256::one(),
+                ..Default::default()
+            };
+            contract.set_balance(&caller, total_supply);
             Self::env().emit_event(Transfer { from: None, to: Some(caller), value: total_supply });
-            Self { total_supply, balances, allowances: Default::default() }
+            contract
+        }
+
+        /// Deploys with no supply and `migration_mode` on, so the owner can `seed_balance` a snapshot.
+        #[ink(constructor)]
+        pub fn for_migration(name: String, symbol: String, decimals: u8) -> Self {
+            let mut contract = Self::with_metadata(U256::zero(), name, symbol, decimals);
+            contract.migration_mode = true;
+            contract
+        }
+
+        /// Deploys as the successor of `predecessor`, recording it for upgrade tooling.
+        #[ink(constructor)]
+        pub fn upgraded_from(total_supply: U256, predecessor: Address) -> Self {
+            let mut contract = Self::new(total_supply);
+            contract.migrated_from = Some(predecessor);
+            contract
         }
 
+        #[ink(message)]
+        pub fn version(&self) -> u32 { VERSION }
+
+        #[ink(message)]
+        pub fn predecessor(&self) -> Option<Address> { self.migrated_from }
+
+        #[ink(message)]
+        pub fn name(&self) -> String { self.name.clone() }
+
+        #[ink(message)]
+        pub fn symbol(&self) -> String { self.symbol.clone() }
+
+        #[ink(message)]
+        pub fn decimals(&self) -> u8 { self.decimals }
+
         #[ink(message)]
         pub fn total_supply(&self) -> U256 { self.total_supply }
 
+        /// Number of accounts with a non-zero stored balance.
         #[ink(message)]
-        pub fn balance_of(&self, owner: Address) -> U256 { self.balance_of_impl(&owner) }
+        pub fn holder_count(&self) -> u32 { self.holder_count }
 
-        #[inline]
-        fn balance_of_impl(&self, owner: &Address) -> U256 { self.balances.get(owner).unwrap_or_default() }
+        /// Holders at positions `offset..offset + limit` of the holder list, skipping those now at zero balance.
+        #[ink(message)]
+        pub fn holders_page(&self, offset: u32, limit: u32) -> Vec<(Address, U256)> {
+            let end = offset.saturating_add(limit).min(self.holder_list.len());
+            (offset..end)
+                .filter_map(|i| self.holder_list.get(i))
+                .map(|holder| (holder, self.balance_of_impl(&holder)))
+                .filter(|(_, balance)| !balance.is_zero())
+                .collect()
+        }
 
+        /// When `account` first received tokens.
         #[ink(message)]
-        pub fn allowance(&self, owner: Address, spender: Address) -> U256 { self.allowance_impl(&owner, &spender) }
+        pub fn first_seen(&self, account: Address) -> Option<u64> { self.first_seen.get(account) }
 
-        #[inline]
-        fn allowance_impl(&self, owner: &Address, spender: &Address) -> U256 { self.allowances.get((owner, spender)).unwrap_or_default() }
+        #[ink(message)]
+        pub fn holding_duration(&self, account: Address) -> u64 {
+            self.first_seen(account).map_or(0, |since| self.env().block_timestamp().saturating_sub(since))
+        }
 
+        /// Bonus in bps growing by `bps_per_day` for each full day held, up to `cap_bps`.
         #[ink(message)]
-        pub fn transfer(&mut self, to: Address, value: U256) -> Result<()> {
-            let from = self.env().caller();
-            self.transfer_from_to(&from, &to, value)
+        pub fn set_loyalty_params(&mut self, bps_per_day: u32, cap_bps: u32) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.loyalty_bps_per_day = bps_per_day;
+            self.loyalty_cap_bps = cap_bps;
+            Ok(())
         }
 
         #[ink(message)]
-        pub fn approve(&mut self, spender: Address, value: U256) -> Result<()> {
-            let owner = self.env().caller();
-            self.allowances.insert((&owner, &spender), &value);
-            self.env().emit_event(Approval { owner, spender, value });
+        pub fn loyalty_multiplier(&self, account: Address) -> u32 {
+            let days = self.holding_duration(account) / SECONDS_PER_DAY;
+            u32::try_from(days).unwrap_or(u32::MAX).saturating_mul(self.loyalty_bps_per_day).min(self.loyalty_cap_bps)
+        }
+
+        /// `account`'s balance as basis points of the total supply.
+        #[ink(message)]
+        pub fn supply_share_bps(&self, account: Address) -> u32 {
+            if self.total_supply.is_zero() { return 0 }
+            let share = self.balance_of_impl(&account).saturating_mul(U256::from(MAX_BPS)) / self.total_supply;
+            share.min(U256::from(MAX_BPS)).as_u32()
+        }
+
+        /// Whether `accounts`, together with the contract's own escrow balance, hold exactly the total supply.
+        /// Only meaningful when `accounts` lists every holder; reflection rounding can leave it short by dust.
+        #[ink(message)]
+        pub fn check_invariant(&self, accounts: Vec<Address>) -> bool {
+            let mut accounts = accounts;
+            accounts.push(self.env().address());
+            accounts.sort();
+            accounts.dedup();
+            self.total_held_by(accounts) == Ok(self.total_supply)
+        }
+
+        #[ink(message)]
+        pub fn total_held_by(&self, addresses: Vec<Address>) -> Result<U256> {
+            addresses.iter().try_fold(U256::zero(), |sum, account| sum.checked_add(self.balance_of_impl(account)).ok_or(Error::Overflow))
+        }
+
+        #[ink(message)]
+        pub fn delegates(&self, account: Address) -> Option<Address> { self.delegates.get(account) }
+
+        /// Lends the caller's balance as voting power to `delegatee`, moving it off any previous delegate.
+        #[ink(message)]
+        pub fn delegate(&mut self, delegatee: Address) -> Result<()> {
+            let delegator = self.env().caller();
+            let balance = self.balance_of_impl(&delegator);
+            let from_delegate = self.delegates.get(delegator);
+            if let Some(previous) = from_delegate {
+                self.move_voting_weight(&delegator, &previous, U256::zero());
+            }
+            self.delegates.insert(delegator, &delegatee);
+            self.move_voting_weight(&delegator, &delegatee, balance);
+            self.env().emit_event(DelegateChanged { delegator, from_delegate, to_delegate: delegatee });
             Ok(())
         }
 
+        /// Votes delegated to `account`, as of the latest balance writes of its delegators.
         #[ink(message)]
-        pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<()> {
-            let caller = self.env().caller();
-            let allowance = self.allowance_impl(&from, &caller);
-            if allowance < value { return Err(Error::InsufficientAllowance) }
-            self.transfer_from_to(&from, &to, value)?;
-            self.allowances.insert((&from, &caller), &(allowance - value));
+        pub fn get_votes(&self, account: Address) -> U256 {
+            self.vote_checkpoints.get(account).and_then(|checkpoints| checkpoints.last().map(|(_, votes)| *votes)).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_past_votes(&self, account: Address, block: u32) -> U256 {
+            let checkpoints = self.vote_checkpoints.get(account).unwrap_or_default();
+            checkpoints.iter().rev().find(|(at, _)| *at <= block).map_or(U256::zero(), |(_, votes)| *votes)
+        }
+
+        /// `(get_votes(account), get_past_votes(account, block))` in one call.
+        #[ink(message)]
+        pub fn votes_now_and_at(&self, account: Address, block: u32) -> (U256, U256) {
+            (self.get_votes(account), self.get_past_votes(account, block))
+        }
+
+        /// Native balance held by the contract alongside the token supply, for backing ratios.
+        #[ink(message)]
+        pub fn reserves(&self) -> (U256, U256) { (self.env().balance(), self.total_supply) }
+
+        #[ink(message)]
+        pub fn chain_id(&self) -> u64 { self.chain_id }
+
+        /// Sets the chain id committed to by the EIP-712 domain separator.
+        #[ink(message)]
+        pub fn set_chain_id(&mut self, chain_id: u64) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.chain_id = chain_id;
             Ok(())
         }
 
-        fn transfer_from_to(&mut self, from: &Address, to: &Address, value: U256) -> Result<()> {
-            let from_balance = self.balance_of_impl(from);
-            if from_balance < value { return Err(Error::InsufficientBalance) }
-            self.balances.insert(from, &(from_balance - value));
-            let to_balance = self.balance_of_impl(to);
-            self.balances.insert(to, &(to_balance.checked_add(value).unwrap()));
-            self.env().emit_event(Transfer { from: Some(*from), to: Some(*to), value });
+        /// Salt mixed into `balance_commitment`.
+        #[ink(message)]
+        pub fn set_commitment_salt(&mut self, salt: [u8; 32]) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.commitment_salt = salt;
             Ok(())
         }
-    }
-}
 
+        /// `keccak(account ++ balance ++ salt)`, letting a holder prove their balance off-chain by revealing the preimage.
+        #[ink(message)]
+        pub fn balance_commitment(&self, account: Address) -> [u8; 32] {
+            self.commitment_for(&account, self.balance_of_impl(&account))
+        }
+
+        #[ink(message)]
+        pub fn verify_balance_proof(&self, account: Address, claimed_balance: U256) -> bool {
+            self.commitment_for(&account, claimed_balance) == self.balance_commitment(account)
+        }
+
+        fn commitment_for(&self, account: &Address, balance: U256) -> [u8; 32] {
+            self.keccak(&[&account.0[..], &u256_word(balance)[..], &self.commitment_salt[..]].concat())
+        }
+
+        /// Stable identifier for bridge registries: `keccak(address ++ keccak(name) ++ keccak(symbol))`.
+        #[ink(message)]
+        pub fn token_id(&self) -> [u8; 32] {
+            let name = self.keccak(self.name.as_bytes());
+            let symbol = self.keccak(self.symbol.as_bytes());
+            self.keccak(&[&self.env().address().0[..], &name[..], &symbol[..]].concat())
+        }
+
+        /// Returns the EIP-712 domain separator over the token name, `EIP712_VERSION`, chain id and contract address.
+        #[ink(message)]
+        pub fn domain_separator(&self) -> [u8; 32] {
+            self.hash_words(&[
+                self.keccak(EIP712_DOMAIN_TYPE),
+                self.keccak(self.name.as_bytes()),
+                self.keccak(EIP712_VERSION.as_bytes()),
+                u64_word(self.chain_id),
+                address_word(&self.env().address()),
+            ])
+        }
+
+        /// Returns `total_supply` as a decimal string with `decimals` fractional digits, e.g. "15.00".
+        #[ink(message)]
+        pub fn total_supply_formatted(&self) -> String {
+            if self.total_supply.is_zero() { return String::from("0") }
+            let digits = self.total_supply.to_string();
+            let decimals = usize::from(self.decimals);
+            if decimals == 0 { return digits }
+            let padded = if digits.len() > decimals { digits } else { format!("{}{}", "0".repeat(decimals + 1 - digits.len()), digits) };
+            let (whole, fraction) = padded.split_at(padded.len() - decimals);
+            format!("{whole}.{fraction}")
+        }
+
+        #[ink(message)]
+        pub fn balance_of(&self, owner: Address) -> U256 { self.balance_of_impl(&owner) }
+
+        /// Balances are stored in the units of the last redenomination that touched them and rescaled on read.
+        /// Reflections accrued since the last write are added on top.
+        fn balance_of_impl(&self, owner: &Address) -> U256 {
+            let stored = self.balances.get(owner).unwrap_or_default();
+            if stored.is_zero() { return stored }
+            let (numerator, denominator) = self.balance_scales.get(owner).unwrap_or((U256::one(), U256::one()));
+            let scaled = if (numerator, denominator) == (self.scale_numerator, self.scale_denominator) {
+                stored
+            } else {
+                let ratio_numerator = self.scale_numerator.saturating_mul(denominator);
+                let ratio_denominator = self.scale_denominator.saturating_mul(numerator);
+                let divisor = gcd(ratio_numerator, ratio_denominator);
+                let (ratio_numerator, ratio_denominator) = (ratio_numerator / divisor, ratio_denominator / divisor);
+                // Dividing first only loses precision for balances too large to multiply out.
+                stored.checked_mul(ratio_numerator).map_or_else(|| stored / ratio_denominator * ratio_numerator, |value| value / ratio_denominator)
+            };
+            scaled + self.pending_reflection(owner, scaled)
+        }
+
+        fn pending_reflection(&self, owner: &Address, balance: U256) -> U256 {
+            if self.is_excluded_from_reflection(*owner) { return U256::zero() }
+            let snapshot = self.reflection_snapshots.get(owner).unwrap_or_default();
+            balance.saturating_mul(self.reflection_per_token - snapshot) / U256::from(RATE_PRECISION)
+        }
+
+        /// Zero balances are removed from storage rather than stored.
+        fn set_balance(&mut self, owner: &Address, value: U256) {
+            let had_balance = self.balances.contains(owner);
+            if value.is_zero() {
+                self.balances.remove(owner);
+                self.balance_scales.remove(owner);
+                self.reflection_snapshots.remove(owner);
+                if had_balance { self.holder_count -= 1; }
+            } else {
+                self.balances.insert(owner, &value);
+                if !had_balance { self.holder_count += 1; }
+                if !self.is_holder.contains(owner) {
+                    self.holder_list.push(owner);
+                    self.is_holder.insert(owner, &true);
+                    self.first_seen.insert(owner, &self.env().block_timestamp());
+                }
+                if self.scale_numerator == U256::one() && self.scale_denominator == U256::one() {
+                    self.balance_scales.remove(owner);
+                } else {
+                    self.balance_scales.insert(owner, &(self.scale_numerator, self.scale_denominator));
+                }
+                if self.reflection_per_token.is_zero() {
+                    self.reflection_snapshots.remove(owner);
+                } else {
+                    self.reflection_snapshots.insert(owner, &self.reflection_per_token);
+                }
+            }
+            if let Some(delegatee) = self.delegates.get(owner) {
+                self.move_voting_weight(owner, &delegatee, value);
+            }
+        }
+
+        /// Replaces the weight `owner` lends `delegatee` with `weight`, checkpointing the delegatee's votes.
+        fn move_voting_weight(&mut self, owner: &Address, delegatee: &Address, weight: U256) {
+            let previous = self.voting_weight.get(owner).unwrap_or_default();
+            if previous == weight { return }
+            let votes = self.get_votes(*delegatee).saturating_sub(previous).saturating_add(weight);
+            self.write_checkpoint(delegatee, votes);
+            self.voting_weight.insert(owner, &weight);
+        }
+
+        fn write_checkpoint(&mut self, account: &Address, votes: U256) {
+            let block = self.env().block_number();
+            let mut checkpoints = self.vote_checkpoints.get(account).unwrap_or_default();
+            match checkpoints.last_mut() {
+                Some((last_block, last_votes)) if *last_block == block => *last_votes = votes,
+                _ => checkpoints.push((block, votes)),
+            }
+            self.vote_checkpoints.insert(account, &checkpoints);
+        }
+
+        /// Folds any accrued reflection into the stored balance, e.g. before the account's exclusion changes.
+        fn settle_balance(&mut self, owner: &Address) {
+            let balance = self.balance_of_impl(owner);
+            self.set_balance(owner, balance);
+        }
+
+        #[ink(message)]
+        pub fn allowance(&self, owner: Address, spender: Address) -> U256 { self.allowance_impl(&owner, &spender) }
+
+        /// Whether `spender` may currently move `amount` of `owner`'s tokens, honouring expiry and revocation epochs.
+        #[ink(message)]
+        pub fn has_allowance(&self, owner: Address, spender: Address, amount: U256) -> bool { self.allowance_impl(&owner, &spender) >= amount }
+
+        /// Returns `(total_supply, balance_of(caller), allowance(caller, spender))` in one call.
+        #[ink(message)]
+        pub fn overview(&self, spender: Address) -> (U256, U256, U256) {
+            let caller = self.env().caller();
+            (self.total_supply, self.balance_of_impl(&caller), self.allowance_impl(&caller, &spender))
+        }
+
+        #[ink(message)]
+        pub fn last_transfer_at(&self, account: Address) -> u64 { self.last_transfer_at.get(account).unwrap_or_default() }
+
+        #[ink(message)]
+        pub fn account_state(&self, owner: Address) -> AccountSnapshot {
+            AccountSnapshot {
+                balance: self.balance_of_impl(&owner),
+                fee_exempt: self.is_fee_exempt(owner),
+                airdrop_allocation: self.airdrop_allocation(owner),
+                last_transfer_at: self.last_transfer_at(owner),
+                frozen: self.is_frozen(owner),
+                blacklisted: self.is_blacklisted(owner),
+            }
+        }
+
+        /// `(balance_of(owner), allowance(owner, spender))` for each pair, in order.
+        #[ink(message)]
+        pub fn snapshot_pairs(&self, pairs: Vec<(Address, Address)>) -> Vec<(U256, U256)> {
+            pairs.iter().map(|(owner, spender)| (self.balance_of_impl(owner), self.allowance_impl(owner, spender))).collect()
+        }
+
+        /// The most `spender` can currently pull from `owner`: `min(balance, allowance)`.
+        #[ink(message)]
+        pub fn max_pullable(&self, owner: Address, spender: Address) -> U256 {
+            self.balance_of_impl(&owner).min(self.allowance_impl(&owner, &spender))
+        }
+
+        #[inline]
+        /// Allowances granted before the current `approval_epoch` or past their expiry count as zero.
+        fn allowance_impl(&self, owner: &Address, spender: &Address) -> U256 {
+            if self.allowance_epochs.get((owner, spender)).unwrap_or_default() < self.approval_epoch { return U256::zero() }
+            if self.is_allowance_expired(owner, spender) { return U256::zero() }
+            self.allowances.get((owner, spender)).unwrap_or_default()
+        }
+
+        fn is_allowance_expired(&self, owner: &Address, spender: &Address) -> bool {
+            matches!(self.allowance_expiries.get((owner, spender)), Some(expires_at) if self.env().block_timestamp() >= expires_at)
+        }
 
+        #[ink(message)]
+        pub fn allowance_expiry(&self, owner: Address, spender: Address) -> Option<u64> { self.allowance_expiries.get((owner, spender)) }
 
+        /// Like `approve`, but the allowance counts as zero from `expires_at` onwards.
+        #[ink(message)]
+        pub fn approve_with_expiry(&mut self, spender: Address, value: U256, expires_at: u64) -> Result<()> {
+            self.approve(spender, value)?;
+            let owner = self.env().caller();
+            self.allowance_expiries.insert((owner, spender), &expires_at);
+            Ok(())
+        }
+
+        /// Clears an expired allowance from storage, emitting an `Approval` of zero.
+        #[ink(message)]
+        pub fn reclaim_stale_allowance(&mut self, owner: Address, spender: Address) -> Result<()> {
+            self.ensure_owner()?;
+            if !self.is_allowance_expired(&owner, &spender) { return Err(Error::AllowanceNotStale) }
+            self.approve_impl(&owner, &spender, U256::zero());
+            self.allowances.remove((owner, spender));
+            self.allowance_expiries.remove((owner, spender));
+            self.approved_at.remove((owner, spender));
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn approval_epoch(&self) -> u64 { self.approval_epoch }
+
+        #[ink(message)]
+        pub fn approval_cooldown(&self) -> u64 { self.approval_cooldown }
+
+        /// Seconds a newly granted allowance must wait before `transfer_from` can spend it.
+        #[ink(message)]
+        pub fn set_approval_cooldown(&mut self, cooldown: u64) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.approval_cooldown = cooldown;
+            Ok(())
+        }
+
+        /// Starts a new approval epoch, voiding every allowance granted so far.
+        #[ink(message)]
+        pub fn invalidate_all_allowances(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            self.approval_epoch = self.approval_epoch.checked_add(1).ok_or(Error::Overflow)?;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn features(&self) -> FeatureFlags {
+            FeatureFlags {
+                has_fee: self.current_fee_bps() > 0 || !self.fee_tiers.is_empty(),
+                fee_holiday: self.in_fee_holiday(),
+                indexed_transfers: self.indexed_transfers,
+                transfers_per_block_capped: self.max_transfers_per_block > 0,
+                transfers_paused: self.transfers_paused,
+                approvals_paused: self.approvals_paused,
+                mints_paused: self.mints_paused,
+                config_locked: self.config_locked,
+                whitelist_mode: self.whitelist_mode,
+                blacklist_active: self.blacklisted_count > 0,
+                has_cap: self.cap.is_some(),
+            }
+        }
+
+        #[ink(message)]
+        pub fn owner(&self) -> Address { self.owner }
+
+        #[ink(message)]
+        pub fn is_owner(&self) -> bool { self.env().caller() == self.owner }
+
+        #[ink(message)]
+        pub fn migration_mode(&self) -> bool { self.migration_mode }
+
+        /// Credits `value` to `account` from the migration snapshot, bypassing mint and transfer restrictions.
+        #[ink(message)]
+        pub fn seed_balance(&mut self, account: Address, value: U256) -> Result<()> {
+            self.ensure_owner()?;
+            if !self.migration_mode { return Err(Error::MigrationClosed) }
+            self.total_supply = self.total_supply.checked_add(value).ok_or(Error::Overflow)?;
+            self.credit(&account, value)?;
+            self.env().emit_event(Transfer { from: None, to: Some(account), value });
+            Ok(())
+        }
+
+        /// Permanently disables `seed_balance`.
+        #[ink(message)]
+        pub fn finalize_migration(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            self.migration_mode = false;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn treasury(&self) -> Address { self.treasury }
+
+        #[ink(message)]
+        pub fn set_treasury(&mut self, treasury: Address) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.treasury = treasury;
+            Ok(())
+        }
+
+        /// Burns `amount` of the fee collector's balance, standing in for buying tokens back with collected fees.
+        #[ink(message)]
+        pub fn buyback_and_burn(&mut self, amount: U256) -> Result<()> {
+            self.ensure_owner()?;
+            let collector = self.fee_collector;
+            self.burn_from_account(&collector, amount)?;
+            self.env().emit_event(BuybackBurn { amount });
+            Ok(())
+        }
+
+        /// Burns from the treasury exactly enough to bring the supply down to `target_supply`.
+        #[ink(message)]
+        pub fn burn_to_target(&mut self, target_supply: U256) -> Result<()> {
+            self.ensure_owner()?;
+            let excess = self.total_supply.checked_sub(target_supply).ok_or(Error::TargetAboveSupply)?;
+            let treasury = self.treasury;
+            self.burn_from_account(&treasury, excess)
+        }
+
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<()> {
+            self.ensure_owner()?;
+            let previous_owner = self.owner;
+            self.settle_balance(&previous_owner);
+            self.settle_balance(&new_owner);
+            self.owner = new_owner;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn config_locked(&self) -> bool { self.config_locked }
+
+        /// Permanently freezes every parameter setter. Ownership transfer, the pause flags,
+        /// airdrop allocations and redenomination remain available to the owner.
+        #[ink(message)]
+        pub fn lock_configuration(&mut self) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.config_locked = true;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn transfers_paused(&self) -> bool { self.transfers_paused }
+
+        #[ink(message)]
+        pub fn set_transfers_paused(&mut self, paused: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.transfers_paused = paused;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn breaker_threshold_bps(&self) -> u16 { self.breaker_threshold_bps }
+
+        /// Pauses transfers after any non-owner transfer larger than `threshold_bps` of the supply; zero disables it.
+        #[ink(message)]
+        pub fn set_breaker_threshold_bps(&mut self, threshold_bps: u16) -> Result<()> {
+            self.ensure_config_owner()?;
+            if threshold_bps > MAX_BPS { return Err(Error::InvalidFee) }
+            self.breaker_threshold_bps = threshold_bps;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn would_trip_breaker(&self, value: U256) -> bool {
+            self.breaker_threshold_bps > 0 && value > bps_of(self.total_supply, self.breaker_threshold_bps)
+        }
+
+        #[ink(message)]
+        pub fn is_blacklisted(&self, account: Address) -> bool { self.blacklisted.get(account).unwrap_or_default() }
+
+        /// Blacklisted accounts can neither send nor receive.
+        #[ink(message)]
+        pub fn set_blacklisted(&mut self, account: Address, blacklisted: bool) -> Result<()> {
+            self.ensure_owner()?;
+            let was_blacklisted = self.is_blacklisted(account);
+            if blacklisted {
+                if !was_blacklisted { self.blacklisted_count += 1 }
+                self.blacklisted.insert(account, &true);
+                self.env().emit_event(Blacklisted { account });
+            } else {
+                if was_blacklisted { self.blacklisted_count -= 1 }
+                self.blacklisted.remove(account);
+                self.env().emit_event(Unblacklisted { account });
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn batch_set_blacklist(&mut self, accounts: Vec<Address>, blacklisted: Vec<bool>) -> Result<()> {
+            self.ensure_owner()?;
+            if accounts.len() != blacklisted.len() { return Err(Error::LengthMismatch) }
+            for (account, blacklisted) in accounts.into_iter().zip(blacklisted) {
+                self.set_blacklisted(account, blacklisted)?;
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_frozen(&self, account: Address) -> bool { self.account_frozen.get(account).unwrap_or_default() }
+
+        /// Stops `account` from sending; it can still receive.
+        #[ink(message)]
+        pub fn freeze_account(&mut self, account: Address) -> Result<()> {
+            self.ensure_owner()?;
+            self.account_frozen.insert(account, &true);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn unfreeze_account(&mut self, account: Address) -> Result<()> {
+            self.ensure_owner()?;
+            self.account_frozen.remove(account);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn whitelist_mode(&self) -> bool { self.whitelist_mode }
+
+        /// While enabled, both sides of a transfer must be whitelisted.
+        #[ink(message)]
+        pub fn set_whitelist_mode(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.whitelist_mode = enabled;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn last_price(&self) -> U256 { self.last_price }
+
+        /// Sets the price recorded against tokens received from now on, for cost-basis accounting.
+        #[ink(message)]
+        pub fn set_last_price(&mut self, price: U256) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.last_price = price;
+            Ok(())
+        }
+
+        /// Best-effort weighted-average price `account` paid for its tokens, at the `last_price` of each receipt.
+        #[ink(message)]
+        pub fn cost_basis_of(&self, account: Address) -> U256 { self.cost_basis.get(account).unwrap_or_default() }
+
+        #[ink(message)]
+        pub fn reject_self_transfers(&self) -> bool { self.reject_self_transfers }
+
+        /// When enabled, transfers addressed to this token contract fail instead of being tallied in `received_by_contract`.
+        #[ink(message)]
+        pub fn set_reject_self_transfers(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.reject_self_transfers = enabled;
+            Ok(())
+        }
+
+        /// Tokens users have transferred to this contract's own address, awaiting rescue.
+        #[ink(message)]
+        pub fn received_by_contract(&self) -> U256 { self.received_by_contract }
+
+        /// Returns up to `received_by_contract` of the contract's own balance to `to`; escrowed tokens stay put.
+        #[ink(message)]
+        pub fn rescue_received(&mut self, to: Address, amount: U256) -> Result<()> {
+            self.ensure_owner()?;
+            if amount > self.received_by_contract { return Err(Error::InsufficientBalance) }
+            let this = self.env().address();
+            self.move_tokens(&this, &to, amount)?;
+            self.received_by_contract -= amount;
+            Ok(())
+        }
+
+        /// Whitelists `account` up to and including `timestamp`.
+        #[ink(message)]
+        pub fn whitelist_until(&mut self, account: Address, timestamp: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.whitelisted_until.insert(account, &timestamp);
+            Ok(())
+        }
+
+        /// The owner is always whitelisted.
+        #[ink(message)]
+        pub fn is_whitelisted(&self, account: Address) -> bool {
+            account == self.owner || self.whitelisted_until.get(account).is_some_and(|until| self.env().block_timestamp() <= until)
+        }
+
+        #[ink(message)]
+        pub fn approvals_paused(&self) -> bool { self.approvals_paused }
+
+        /// Blocks granting or raising allowances; lowering and revoking them stays possible.
+        #[ink(message)]
+        pub fn set_approvals_paused(&mut self, paused: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.approvals_paused = paused;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn cap_allowance_to_balance(&self) -> bool { self.cap_allowance_to_balance }
+
+        /// When enabled, `approve` and `increase_allowance` never grant more than the owner's current balance.
+        #[ink(message)]
+        pub fn set_cap_allowance_to_balance(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.cap_allowance_to_balance = enabled;
+            Ok(())
+        }
+
+        /// The owner is always a minter.
+        #[ink(message)]
+        pub fn is_minter(&self, account: Address) -> bool { account == self.owner || self.minters.get(account).unwrap_or_default() }
+
+        #[ink(message)]
+        pub fn set_minter(&mut self, account: Address, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            if enabled {
+                self.minters.insert(account, &true);
+            } else {
+                self.minters.remove(account);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn mint(&mut self, to: Address, value: U256) -> Result<()> {
+            self.ensure_minter()?;
+            self.mint_to(&to, value)
+        }
+
+        /// Mints `total` across all current holders pro-rata to their balances, giving rounding dust to the largest holder.
+        #[ink(message)]
+        pub fn distribute_to_holders(&mut self, total: U256) -> Result<()> {
+            self.ensure_owner()?;
+            // The contract's own balance is escrow (stakes, vesting, pending liquidity), not a holding.
+            let this = self.env().address();
+            let holders: Vec<(Address, U256)> = self.holders_page(0, self.holder_list.len()).into_iter().filter(|(holder, _)| *holder != this).collect();
+            let held = holders.iter().try_fold(U256::zero(), |sum, (_, balance)| sum.checked_add(*balance)).ok_or(Error::Overflow)?;
+            let Some(largest) = holders.iter().max_by_key(|(_, balance)| *balance).map(|(holder, _)| *holder) else { return Err(Error::NoHolders) };
+            if total > self.remaining_mintable() { return Err(Error::CapExceeded) }
+            let mut shares = Vec::with_capacity(holders.len());
+            let mut assigned = U256::zero();
+            for (holder, balance) in holders {
+                let share = total.checked_mul(balance).ok_or(Error::Overflow)? / held;
+                assigned = assigned.checked_add(share).ok_or(Error::Overflow)?;
+                shares.push((holder, share));
+            }
+            for (holder, share) in shares {
+                let share = if holder == largest { share + (total - assigned) } else { share };
+                if !share.is_zero() { self.mint_to(&holder, share)? }
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn mint_proposal(&self, id: u64) -> Option<MintProposal> { self.mint_proposals.get(id) }
+
+        /// Queues a mint that executes once a second minter approves it; the proposer's approval is implied.
+        #[ink(message)]
+        pub fn propose_mint(&mut self, to: Address, value: U256) -> Result<u64> {
+            self.ensure_minter()?;
+            let id = self.next_mint_proposal;
+            self.next_mint_proposal = id.checked_add(1).ok_or(Error::Overflow)?;
+            let proposal = MintProposal { to, value, approvals: vec![self.env().caller()], executed: false };
+            self.mint_proposals.insert(id, &proposal);
+            Ok(id)
+        }
+
+        #[ink(message)]
+        pub fn approve_mint(&mut self, id: u64) -> Result<()> {
+            self.ensure_minter()?;
+            let mut proposal = self.mint_proposals.get(id).ok_or(Error::UnknownProposal)?;
+            if proposal.executed { return Err(Error::ProposalExecuted) }
+            let signer = self.env().caller();
+            if proposal.approvals.contains(&signer) { return Err(Error::AlreadyApproved) }
+            proposal.approvals.push(signer);
+            if proposal.approvals.len() >= 2 {
+                self.mint_to(&proposal.to, proposal.value)?;
+                proposal.executed = true;
+            }
+            self.mint_proposals.insert(id, &proposal);
+            Ok(())
+        }
+
+        /// Like `mint`, additionally emitting a `ReasonedMint` for audit trails.
+        #[ink(message)]
+        pub fn mint_with_reason(&mut self, to: Address, value: U256, reason: String) -> Result<()> {
+            self.mint(to, value)?;
+            self.env().emit_event(ReasonedMint { to, value, reason });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn cap(&self) -> Option<U256> { self.cap }
+
+        /// Caps the supply mints can reach; it cannot be set below the current supply.
+        #[ink(message)]
+        pub fn set_cap(&mut self, cap: Option<U256>) -> Result<()> {
+            self.ensure_config_owner()?;
+            if cap.is_some_and(|cap| cap < self.total_supply) { return Err(Error::CapExceeded) }
+            self.cap = cap;
+            Ok(())
+        }
+
+        /// Headroom left under the cap, or `U256::MAX` when uncapped.
+        #[ink(message)]
+        pub fn remaining_mintable(&self) -> U256 {
+            self.cap.map_or(U256::MAX, |cap| cap.saturating_sub(self.total_supply))
+        }
+
+        #[ink(message)]
+        pub fn mints_paused(&self) -> bool { self.mints_paused }
+
+        #[ink(message)]
+        pub fn set_mints_paused(&mut self, paused: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.mints_paused = paused;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn fee_bps(&self) -> u16 { self.fee_bps }
+
+        #[ink(message)]
+        pub fn set_fee(&mut self, fee_bps: u16) -> Result<()> {
+            self.ensure_config_owner()?;
+            if fee_bps > MAX_BPS { return Err(Error::InvalidFee) }
+            self.fee_bps = fee_bps;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn fee_collector(&self) -> Address { self.fee_collector }
+
+        #[ink(message)]
+        pub fn set_fee_collector(&mut self, collector: Address) -> Result<()> {
+            self.ensure_config_owner()?;
+            let previous_collector = self.fee_collector;
+            self.settle_balance(&previous_collector);
+            self.settle_balance(&collector);
+            self.fee_collector = collector;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn reflection_bps(&self) -> u16 { self.reflection_bps }
+
+        /// Share of each non-exempt transfer redistributed pro rata to holders not excluded from reflection.
+        #[ink(message)]
+        pub fn set_reflection_bps(&mut self, reflection_bps: u16) -> Result<()> {
+            self.ensure_config_owner()?;
+            if reflection_bps > MAX_BPS { return Err(Error::InvalidFee) }
+            self.reflection_bps = reflection_bps;
+            Ok(())
+        }
+
+        /// The owner, the fee collector, the contract itself and any explicitly excluded account never accrue reflections.
+        #[ink(message)]
+        pub fn is_excluded_from_reflection(&self, account: Address) -> bool {
+            account == self.owner || account == self.fee_collector || account == self.env().address() || self.reflection_excluded.contains(&account)
+        }
+
+        /// Stops `account` (e.g. an exchange hot wallet) accruing reflections, keeping what it accrued so far.
+        #[ink(message)]
+        pub fn exclude_from_reflection(&mut self, account: Address) -> Result<()> {
+            self.ensure_owner()?;
+            if self.reflection_excluded.contains(&account) { return Ok(()) }
+            let balance = self.balance_of_impl(&account);
+            self.reflection_excluded.push(account);
+            self.set_balance(&account, balance);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn include_in_reflection(&mut self, account: Address) -> Result<()> {
+            self.ensure_owner()?;
+            let balance = self.balance_of_impl(&account);
+            self.reflection_excluded.retain(|excluded| *excluded != account);
+            self.set_balance(&account, balance);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn fee_split(&self) -> Option<(Address, Address, u16)> { self.fee_split }
+
+        /// Divides collected fees between two collectors, `split_bps` of each fee (plus rounding) going to `collector_a`.
+        /// Takes precedence over `fee_collector` until cleared.
+        #[ink(message)]
+        pub fn set_fee_split(&mut self, collector_a: Address, collector_b: Address, split_bps: u16) -> Result<()> {
+            self.ensure_config_owner()?;
+            if split_bps > MAX_BPS { return Err(Error::InvalidFee) }
+            self.fee_split = Some((collector_a, collector_b, split_bps));
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn clear_fee_split(&mut self) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.fee_split = None;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn liquidity_bps(&self) -> u16 { self.liquidity_bps }
+
+        #[ink(message)]
+        pub fn pending_liquidity(&self) -> U256 { self.pending_liquidity }
+
+        /// Diverts `liquidity_bps` of every collected fee to the contract until `add_liquidity` forwards it to `pool`.
+        #[ink(message)]
+        pub fn set_liquidity_config(&mut self, liquidity_bps: u16, pool: Address) -> Result<()> {
+            self.ensure_config_owner()?;
+            if liquidity_bps > MAX_BPS { return Err(Error::InvalidFee) }
+            self.liquidity_bps = liquidity_bps;
+            self.liquidity_pool = Some(pool);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn add_liquidity(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            let pool = self.liquidity_pool.ok_or(Error::NoLiquidityPool)?;
+            let this = self.env().address();
+            let pending = self.pending_liquidity;
+            self.move_tokens(&this, &pool, pending)?;
+            self.pending_liquidity = U256::zero();
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn tax_sink(&self) -> Option<Address> { self.tax_sink }
+
+        /// Routes taxed transfers through `sink`, which keeps the fee and forwards the rest.
+        #[ink(message)]
+        pub fn set_tax_sink(&mut self, sink: Option<Address>) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.tax_sink = sink;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_fee_exempt(&self, account: Address) -> bool { self.fee_exempt.get(account).unwrap_or_default() }
+
+        #[ink(message)]
+        pub fn set_fee_exempt(&mut self, account: Address, exempt: bool) -> Result<()> {
+            self.ensure_config_owner()?;
+            if exempt { self.fee_exempt.insert(account, &true); } else { self.fee_exempt.remove(account); }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn fee_holiday(&self) -> (u64, u64) { (self.fee_holiday_start, self.fee_holiday_end) }
+
+        /// Waives transfer fees for block timestamps in `[start, end)`.
+        #[ink(message)]
+        pub fn set_fee_holiday(&mut self, start: u64, end: u64) -> Result<()> {
+            self.ensure_config_owner()?;
+            if start > end { return Err(Error::InvalidWindow) }
+            self.fee_holiday_start = start;
+            self.fee_holiday_end = end;
+            Ok(())
+        }
+
+        /// Replaces the flat fee with one moving linearly from `start_bps` to `end_bps` over `[start_time, end_time]`.
+        /// Equal times switch the decay off again.
+        #[ink(message)]
+        pub fn set_fee_decay(&mut self, start_bps: u16, end_bps: u16, start_time: u64, end_time: u64) -> Result<()> {
+            self.ensure_config_owner()?;
+            if start_bps > MAX_BPS || end_bps > MAX_BPS { return Err(Error::InvalidFee) }
+            if start_time > end_time { return Err(Error::InvalidWindow) }
+            self.fee_start_bps = start_bps;
+            self.fee_end_bps = end_bps;
+            self.fee_start_time = start_time;
+            self.fee_end_time = end_time;
+            Ok(())
+        }
+
+        /// The fee in force right now: the decay schedule if one is set, otherwise `fee_bps`.
+        #[ink(message)]
+        pub fn current_fee_bps(&self) -> u16 {
+            if self.fee_start_time == self.fee_end_time { return self.fee_bps }
+            let now = self.env().block_timestamp().clamp(self.fee_start_time, self.fee_end_time);
+            let elapsed = u128::from(now - self.fee_start_time);
+            let duration = u128::from(self.fee_end_time - self.fee_start_time);
+            let (start, end) = (u128::from(self.fee_start_bps), u128::from(self.fee_end_bps));
+            let current = if start >= end {
+                start - (start - end) * elapsed / duration
+            } else {
+                start + (end - start) * elapsed / duration
+            };
+            current as u16
+        }
+
+        #[ink(message)]
+        pub fn fee_tiers(&self) -> Vec<(U256, u16)> { self.fee_tiers.clone() }
+
+        /// Replaces the flat fee with size-based tiers: a transfer pays the bps of the highest threshold it reaches,
+        /// nothing below the first. Thresholds must be strictly increasing; an empty list restores the flat fee.
+        #[ink(message)]
+        pub fn set_fee_tiers(&mut self, tiers: Vec<(U256, u16)>) -> Result<()> {
+            self.ensure_config_owner()?;
+            if tiers.iter().any(|(_, bps)| *bps > MAX_BPS) { return Err(Error::InvalidFee) }
+            if tiers.windows(2).any(|pair| pair[0].0 >= pair[1].0) { return Err(Error::InvalidTiers) }
+            self.fee_tiers = tiers;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn holder_fee_tiers(&self) -> Vec<(u64, u16)> { self.holder_fee_tiers.clone() }
+
+        /// Caps the fee for senders by holding duration: one who has held at least a tier's seconds pays at most its bps.
+        /// Thresholds must be strictly increasing; an empty list removes the discount.
+        #[ink(message)]
+        pub fn set_holder_fee_tiers(&mut self, tiers: Vec<(u64, u16)>) -> Result<()> {
+            self.ensure_config_owner()?;
+            if tiers.iter().any(|(_, bps)| *bps > MAX_BPS) { return Err(Error::InvalidFee) }
+            if tiers.windows(2).any(|pair| pair[0].0 >= pair[1].0) { return Err(Error::InvalidTiers) }
+            self.holder_fee_tiers = tiers;
+            Ok(())
+        }
+
+        /// Fee bps `account` currently pays as a sender, after any holding-duration discount.
+        #[ink(message)]
+        pub fn effective_fee_bps(&self, account: Address) -> u16 { self.discounted_fee_bps(&account, self.current_fee_bps()) }
+
+        fn discounted_fee_bps(&self, account: &Address, fee_bps: u16) -> u16 {
+            let held = self.holding_duration(*account);
+            self.holder_fee_tiers.iter().rev().find(|(min_held, _)| held >= *min_held).map_or(fee_bps, |(_, bps)| fee_bps.min(*bps))
+        }
+
+        fn fee_bps_for(&self, value: U256) -> u16 {
+            if self.fee_tiers.is_empty() { return self.current_fee_bps() }
+            self.fee_tiers.iter().rev().find(|(threshold, _)| value >= *threshold).map_or(0, |(_, bps)| *bps)
+        }
+
+        #[ink(message)]
+        pub fn in_fee_holiday(&self) -> bool {
+            let now = self.env().block_timestamp();
+            now >= self.fee_holiday_start && now < self.fee_holiday_end
+        }
+
+        #[ink(message)]
+        pub fn indexed_transfers(&self) -> bool { self.indexed_transfers }
+
+        #[ink(message)]
+        pub fn set_indexed_transfers(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.indexed_transfers = enabled;
+            Ok(())
+        }
+
+        /// The last `RECENT_TRANSFERS_LIMIT` transfers involving `account` as `(counterparty, value, timestamp)`, oldest first.
+        #[ink(message)]
+        pub fn recent_activity(&self, account: Address) -> Vec<(Address, U256, u64)> { self.recent_transfers.get(account).unwrap_or_default() }
+
+        #[ink(message)]
+        pub fn min_hold_secs(&self) -> u64 { self.min_hold_secs }
+
+        /// Seconds an account must hold after each receipt (transfer or mint) before it can send again.
+        #[ink(message)]
+        pub fn set_min_hold_secs(&mut self, min_hold_secs: u64) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.min_hold_secs = min_hold_secs;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn acquired_at(&self, account: Address) -> Option<u64> { self.acquired_at.get(account) }
+
+        #[ink(message)]
+        pub fn referrer_of(&self, account: Address) -> Option<Address> { self.referrers.get(account) }
+
+        /// Records who referred the caller; can only be set once.
+        #[ink(message)]
+        pub fn set_referrer(&mut self, referrer: Address) -> Result<()> {
+            let caller = self.env().caller();
+            if referrer == caller { return Err(Error::SelfReferral) }
+            if self.referrers.contains(caller) { return Err(Error::ReferrerAlreadySet) }
+            self.referrers.insert(caller, &referrer);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn referral_bps(&self) -> u16 { self.referral_bps }
+
+        /// Bonus minted to a sender's referrer on each of their transfers, at most `MAX_REFERRAL_BPS`.
+        #[ink(message)]
+        pub fn set_referral_bps(&mut self, referral_bps: u16) -> Result<()> {
+            self.ensure_config_owner()?;
+            if referral_bps > MAX_REFERRAL_BPS { return Err(Error::InvalidFee) }
+            self.referral_bps = referral_bps;
+            Ok(())
+        }
+
+        /// Number of successful transfers so far.
+        #[ink(message)]
+        pub fn transfer_seq(&self) -> u64 { self.transfer_seq }
+
+        #[ink(message)]
+        pub fn max_transfers_per_block(&self) -> u32 { self.max_transfers_per_block }
+
+        /// Caps how many transfers a non-owner account may send per block; zero disables the cap.
+        #[ink(message)]
+        pub fn set_max_transfers_per_block(&mut self, max: u32) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.max_transfers_per_block = max;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn min_transfer(&self) -> U256 { self.min_transfer }
+
+        /// Rejects non-zero transfers below `min_transfer`; mints and burns are unaffected.
+        #[ink(message)]
+        pub fn set_min_transfer(&mut self, min_transfer: U256) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.min_transfer = min_transfer;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_code_hash_blocked(&self, code_hash: [u8; 32]) -> bool { self.blocked_code_hashes.get(code_hash).unwrap_or_default() }
+
+        /// Blocks transfers to every contract instantiated from `code_hash`.
+        #[ink(message)]
+        pub fn set_code_hash_blocked(&mut self, code_hash: [u8; 32], blocked: bool) -> Result<()> {
+            self.ensure_owner()?;
+            if blocked { self.blocked_code_hashes.insert(code_hash, &true); } else { self.blocked_code_hashes.remove(code_hash); }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn transfer(&mut self, to: Address, value: U256) -> Result<()> {
+            let from = self.env().caller();
+            self.transfer_from_to(&from, &to, value)
+        }
+
+        /// Transfers `value` to `to` and, if `to` is a contract, notifies it via `on_payment(from, value, invoice_id)`.
+        /// A failing callback does not revert the payment; `PaymentCallbackFailed` is emitted instead.
+        #[ink(message)]
+        pub fn pay(&mut self, to: Address, value: U256, invoice_id: u64) -> Result<()> {
+            let from = self.env().caller();
+            self.transfer_from_to(&from, &to, value)?;
+            if self.env().is_contract(&to) {
+                let result = build_call::<Environment>()
+                    .call(to)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("on_payment")))
+                            .push_arg(from)
+                            .push_arg(value)
+                            .push_arg(invoice_id),
+                    )
+                    .ref_time_limit(CALLBACK_REF_TIME_LIMIT)
+                    .proof_size_limit(CALLBACK_PROOF_SIZE_LIMIT)
+                    .storage_deposit_limit(U256::from(CALLBACK_STORAGE_DEPOSIT_LIMIT))
+                    .returns::<()>()
+                    .try_invoke();
+                if !matches!(result, Ok(Ok(()))) {
+                    self.env().emit_event(PaymentCallbackFailed { to, invoice_id });
+                }
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn payment_request(&self, from: Address, to: Address) -> Option<(U256, [u8; 32])> { self.payment_requests.get((from, to)) }
+
+        /// Records that the caller asks `from` for `value`, replacing any earlier request between the two.
+        #[ink(message)]
+        pub fn request_payment(&mut self, from: Address, value: U256, memo: [u8; 32]) -> Result<()> {
+            let to = self.env().caller();
+            self.payment_requests.insert((from, to), &(value, memo));
+            self.env().emit_event(PaymentRequested { from, to, value, memo });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn authorization_state(&self, authorizer: Address, nonce: [u8; 32]) -> bool {
+            self.authorization_state.get((authorizer, nonce)).unwrap_or_default()
+        }
+
+        /// EIP-3009: executes a transfer signed off-chain by `from`, valid strictly between `valid_after` and `valid_before`.
+        #[ink(message)]
+        #[allow(clippy::too_many_arguments)]
+        pub fn transfer_with_authorization(
+            &mut self,
+            from: Address,
+            to: Address,
+            value: U256,
+            valid_after: u64,
+            valid_before: u64,
+            nonce: [u8; 32],
+            signature: [u8; 65],
+        ) -> Result<()> {
+            let now = self.env().block_timestamp();
+            if now <= valid_after { return Err(Error::AuthorizationNotYetValid) }
+            if now >= valid_before { return Err(Error::AuthorizationExpired) }
+            if self.authorization_state(from, nonce) { return Err(Error::AuthorizationUsed) }
+            let struct_hash = self.hash_words(&[
+                self.keccak(TRANSFER_WITH_AUTHORIZATION_TYPE),
+                address_word(&from),
+                address_word(&to),
+                u256_word(value),
+                u64_word(valid_after),
+                u64_word(valid_before),
+                nonce,
+            ]);
+            if self.recover_typed_signer(&struct_hash, &signature)? != from { return Err(Error::InvalidSignature) }
+            self.authorization_state.insert((from, nonce), &true);
+            self.transfer_from_to(&from, &to, value)?;
+            self.env().emit_event(AuthorizationUsed { authorizer: from, nonce });
+            Ok(())
+        }
+
+        /// EIP-3009: burns an unused authorization nonce so it can no longer be executed.
+        #[ink(message)]
+        pub fn cancel_authorization(&mut self, authorizer: Address, nonce: [u8; 32], signature: [u8; 65]) -> Result<()> {
+            if self.authorization_state(authorizer, nonce) { return Err(Error::AuthorizationUsed) }
+            let struct_hash = self.hash_words(&[self.keccak(CANCEL_AUTHORIZATION_TYPE), address_word(&authorizer), nonce]);
+            if self.recover_typed_signer(&struct_hash, &signature)? != authorizer { return Err(Error::InvalidSignature) }
+            self.authorization_state.insert((authorizer, nonce), &true);
+            self.env().emit_event(AuthorizationCanceled { authorizer, nonce });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_registered_receiver(&self, account: Address) -> bool { self.registered_receivers.get(account).unwrap_or_default() }
+
+        /// Declares that the calling contract accepts this token, so `safe_transfer` skips its callback.
+        #[ink(message)]
+        pub fn register_as_receiver(&mut self) {
+            let caller = self.env().caller();
+            self.registered_receivers.insert(caller, &true);
+        }
+
+        /// Transfers `value` to `to`, requiring unregistered contract recipients to accept it via `on_token_received(from, value)`.
+        #[ink(message)]
+        pub fn safe_transfer(&mut self, to: Address, value: U256) -> Result<()> {
+            let from = self.env().caller();
+            self.transfer_from_to(&from, &to, value)?;
+            if self.env().is_contract(&to) && !self.is_registered_receiver(to) {
+                let result = build_call::<Environment>()
+                    .call(to)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("on_token_received")))
+                            .push_arg(from)
+                            .push_arg(value),
+                    )
+                    .returns::<()>()
+                    .try_invoke();
+                if !matches!(result, Ok(Ok(()))) { return Err(Error::ReceiverRejected) }
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn registry(&self) -> Option<Address> { self.registry }
+
+        /// Sets the analytics registry notified after every transfer, or disables notifications with `None`.
+        #[ink(message)]
+        pub fn set_registry(&mut self, registry: Option<Address>) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.registry = registry;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn resolve_alias(&self, name: [u8; 32]) -> Option<Address> { self.aliases.get(name) }
+
+        #[ink(message)]
+        pub fn set_alias(&mut self, name: [u8; 32], addr: Address) -> Result<()> {
+            self.ensure_owner()?;
+            self.aliases.insert(name, &addr);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn transfer_to_alias(&mut self, name: [u8; 32], value: U256) -> Result<()> {
+            let to = self.resolve_alias(name).ok_or(Error::UnknownAlias)?;
+            self.transfer(to, value)
+        }
+
+        /// `(amount_received, fee)` for a transfer, where `fee` is everything withheld from the recipient (fee plus reflection).
+        #[ink(message)]
+        pub fn quote_transfer(&self, from: Address, to: Address, value: U256) -> (U256, U256) {
+            let fee = self.transfer_fee(&from, &to, value);
+            let withheld = fee + self.transfer_reflection(&from, &to, value - fee);
+            (value - withheld, withheld)
+        }
+
+        /// `Ok(())` if the caller could transfer `value` to `to` right now, otherwise the error the transfer would return.
+        #[ink(message)]
+        pub fn can_transfer(&self, to: Address, value: U256) -> Result<()> {
+            self.check_transfer(&self.env().caller(), &to, value).map(|_| ())
+        }
+
+        /// Every `RESTRICTION_*` code currently stopping `account` from sending, for explaining failed transfers.
+        #[ink(message)]
+        pub fn restrictions_for(&self, account: Address) -> Vec<u8> {
+            [
+                (self.transfers_paused, RESTRICTION_PAUSED),
+                (self.is_blacklisted(account), RESTRICTION_BLACKLISTED),
+                (self.is_frozen(account), RESTRICTION_FROZEN),
+                (self.in_hold_period(&account), RESTRICTION_HOLD_PERIOD),
+                (self.whitelist_mode && !self.is_whitelisted(account), RESTRICTION_NOT_WHITELISTED),
+                (self.next_block_transfer_count(&account).is_err(), RESTRICTION_BLOCK_LIMIT),
+            ]
+            .into_iter()
+            .filter_map(|(applies, code)| applies.then_some(code))
+            .collect()
+        }
+
+        /// Balances of `from` and `to` after a hypothetical transfer, or the error it would fail with.
+        #[ink(message)]
+        pub fn simulate_transfer(&self, from: Address, to: Address, value: U256) -> Result<(U256, U256)> {
+            let from_balance = self.check_transfer(&from, &to, value)?;
+            let fee = self.transfer_fee(&from, &to, value);
+            let received = value - fee - self.transfer_reflection(&from, &to, value - fee);
+            if from == to { return Ok((from_balance - value + received, from_balance - value + received)) }
+            Ok((from_balance - value, self.balance_of_impl(&to) + received))
+        }
+
+        /// Transfers `value` to `to` unless the block timestamp is already past `deadline`.
+        #[ink(message)]
+        pub fn transfer_with_deadline(&mut self, to: Address, value: U256, deadline: u64) -> Result<()> {
+            if self.env().block_timestamp() > deadline { return Err(Error::DeadlinePassed) }
+            self.transfer(to, value)
+        }
+
+        /// Transfers the caller's entire balance to `to`; any fee comes out of that amount.
+        #[ink(message)]
+        pub fn transfer_all(&mut self, to: Address) -> Result<()> {
+            let from = self.env().caller();
+            let balance = self.balance_of_impl(&from);
+            self.transfer_from_to(&from, &to, balance)
+        }
+
+        /// Splits `total` across `recipients` in proportion to `weights`; the last recipient takes the rounding remainder.
+        #[ink(message)]
+        pub fn transfer_split(&mut self, recipients: Vec<Address>, weights: Vec<u32>, total: U256) -> Result<()> {
+            if recipients.len() != weights.len() { return Err(Error::LengthMismatch) }
+            let total_weight = weights.iter().fold(U256::zero(), |sum, weight| sum + U256::from(*weight));
+            if total_weight.is_zero() { return Err(Error::ZeroWeight) }
+            let from = self.env().caller();
+            if self.balance_of_impl(&from) < total { return Err(Error::InsufficientBalance) }
+            let mut remaining = total;
+            let last = recipients.len() - 1;
+            for (i, (to, weight)) in recipients.iter().zip(weights).enumerate() {
+                let share = if i == last { remaining } else { total.checked_mul(U256::from(weight)).ok_or(Error::Overflow)? / total_weight };
+                remaining -= share;
+                self.transfer_from_to(&from, to, share)?;
+            }
+            Ok(())
+        }
+
+        /// Proposes `sub` as a sub-account of the caller; the link takes effect once `sub` calls `confirm_link`.
+        #[ink(message)]
+        pub fn link_subaccount(&mut self, sub: Address) -> Result<()> {
+            let main = self.env().caller();
+            if sub == main || self.main_accounts.contains(sub) { return Err(Error::AlreadyLinked) }
+            self.pending_links.insert(sub, &main);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn confirm_link(&mut self, main: Address) -> Result<()> {
+            let sub = self.env().caller();
+            if self.pending_links.get(sub) != Some(main) { return Err(Error::NoPendingLink) }
+            self.pending_links.remove(sub);
+            self.main_accounts.insert(sub, &main);
+            let mut subs = self.subaccounts.get(main).unwrap_or_default();
+            subs.push(sub);
+            self.subaccounts.insert(main, &subs);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn main_account_of(&self, sub: Address) -> Option<Address> { self.main_accounts.get(sub) }
+
+        #[ink(message)]
+        pub fn subaccounts_of(&self, main: Address) -> Vec<Address> { self.subaccounts.get(main).unwrap_or_default() }
+
+        /// Balance of `main` plus all of its confirmed sub-accounts.
+        #[ink(message)]
+        pub fn aggregate_balance(&self, main: Address) -> U256 {
+            self.subaccounts_of(main).iter().fold(self.balance_of_impl(&main), |sum, sub| sum + self.balance_of_impl(sub))
+        }
+
+        #[ink(message)]
+        pub fn approve(&mut self, spender: Address, value: U256) -> Result<()> {
+            let owner = self.env().caller();
+            if self.approvals_paused && value > self.allowance_impl(&owner, &spender) { return Err(Error::ApprovalsPaused) }
+            let value = self.capped_allowance(&owner, value);
+            self.approve_impl(&owner, &spender, value);
+            self.allowance_expiries.remove((owner, spender));
+            self.single_use.remove((owner, spender));
+            Ok(())
+        }
+
+        /// Sets an allowance per spender, requiring the caller to hold at least their combined total.
+        #[ink(message)]
+        pub fn approve_and_distribute(&mut self, spenders: Vec<Address>, amounts: Vec<U256>) -> Result<()> {
+            if spenders.len() != amounts.len() { return Err(Error::LengthMismatch) }
+            let owner = self.env().caller();
+            let total = amounts.iter().try_fold(U256::zero(), |sum, amount| sum.checked_add(*amount)).ok_or(Error::Overflow)?;
+            if total > self.balance_of_impl(&owner) { return Err(Error::ApprovalExceedsBalance) }
+            for (spender, amount) in spenders.into_iter().zip(amounts) {
+                self.approve(spender, amount)?;
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn increase_allowance(&mut self, spender: Address, delta: U256) -> Result<()> {
+            if self.approvals_paused { return Err(Error::ApprovalsPaused) }
+            let owner = self.env().caller();
+            let allowance = self.allowance_impl(&owner, &spender).checked_add(delta).ok_or(Error::Overflow)?;
+            let allowance = self.capped_allowance(&owner, allowance);
+            self.approve_impl(&owner, &spender, allowance);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn decrease_allowance(&mut self, spender: Address, delta: U256) -> Result<()> {
+            let owner = self.env().caller();
+            let allowance = self.allowance_impl(&owner, &spender).checked_sub(delta).ok_or(Error::InsufficientAllowance)?;
+            self.approve_impl(&owner, &spender, allowance);
+            Ok(())
+        }
+
+        /// Raw sum of the allowances every owner has granted to `spender`, saturating at `U256::MAX`.
+        /// Entries voided by an epoch bump or past their expiry still count until overwritten or
+        /// reclaimed, so this is an upper bound on what `spender` can actually move.
+        #[ink(message)]
+        pub fn total_granted_to(&self, spender: Address) -> U256 { self.total_allowance_granted.get(spender).unwrap_or_default() }
+
+        #[ink(message)]
+        pub fn approved_spenders(&self, owner: Address) -> Vec<Address> { self.approved_spenders.get(owner).unwrap_or_default() }
+
+        /// Every non-zero allowance `owner` has granted, as `(spender, value)` pairs.
+        #[ink(message)]
+        pub fn export_allowances(&self, owner: Address) -> Vec<(Address, U256)> {
+            self.approved_spenders(owner)
+                .into_iter()
+                .map(|spender| (spender, self.allowance_impl(&owner, &spender)))
+                .filter(|(_, value)| !value.is_zero())
+                .collect()
+        }
+
+        /// Restores allowances from `export_allowances` on `owner`'s behalf, e.g. after a migration.
+        #[ink(message)]
+        pub fn import_allowances(&mut self, owner: Address, entries: Vec<(Address, U256)>) -> Result<()> {
+            self.ensure_owner()?;
+            for (spender, value) in entries {
+                self.approve_impl(&owner, &spender, value);
+            }
+            Ok(())
+        }
+
+        /// Zeroes every allowance the caller has granted, emitting an `Approval` of zero per spender.
+        #[ink(message)]
+        pub fn revoke_all_approvals(&mut self) -> Result<()> {
+            let owner = self.env().caller();
+            for spender in self.approved_spenders(owner) {
+                self.approve_impl(&owner, &spender, U256::zero());
+            }
+            self.approved_spenders.remove(owner);
+            Ok(())
+        }
+
+        /// Zeroes the caller's allowance to each listed spender, emitting an `Approval` of zero per spender.
+        #[ink(message)]
+        pub fn revoke_approvals(&mut self, spenders: Vec<Address>) -> Result<()> {
+            let owner = self.env().caller();
+            for spender in &spenders {
+                self.approve_impl(&owner, spender, U256::zero());
+            }
+            let remaining: Vec<Address> = self.approved_spenders(owner).into_iter().filter(|spender| !spenders.contains(spender)).collect();
+            self.approved_spenders.insert(owner, &remaining);
+            Ok(())
+        }
+
+        /// Sets the allowance to `new_value` only if it currently equals `expected_current`.
+        #[ink(message)]
+        pub fn compare_and_approve(&mut self, spender: Address, expected_current: U256, new_value: U256) -> Result<()> {
+            let owner = self.env().caller();
+            if self.allowance_impl(&owner, &spender) != expected_current { return Err(Error::AllowanceMismatch) }
+            self.approve(spender, new_value)
+        }
+
+        #[ink(message)]
+        pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<()> {
+            let caller = self.env().caller();
+            let allowance = self.allowance_impl(&from, &caller);
+            if allowance < value { return Err(Error::InsufficientAllowance) }
+            let approved_at = self.approved_at.get((from, caller)).unwrap_or_default();
+            if self.env().block_timestamp() < approved_at.saturating_add(self.approval_cooldown) { return Err(Error::ApprovalTooRecent) }
+            if self.spend_destinations.get((from, caller)).is_some_and(|allowed| allowed != to) { return Err(Error::DestinationRestricted) }
+            self.spend_under_policy(&from, &caller, value)?;
+            self.spend_under_rate_limit(&caller, value)?;
+            self.transfer_from_to(&from, &to, value)?;
+            if self.single_use.take((from, caller)).unwrap_or_default() {
+                self.set_allowance(&from, &caller, U256::zero());
+            } else {
+                self.set_allowance(&from, &caller, allowance - value);
+            }
+            Ok(())
+        }
+
+        /// Like `approve`, but the first `transfer_from` by `spender` zeroes whatever allowance remains.
+        #[ink(message)]
+        pub fn approve_once(&mut self, spender: Address, value: U256) -> Result<()> {
+            self.approve(spender, value)?;
+            let owner = self.env().caller();
+            self.single_use.insert((owner, spender), &true);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_single_use(&self, owner: Address, spender: Address) -> bool { self.single_use.get((owner, spender)).unwrap_or_default() }
+
+        #[ink(message)]
+        pub fn spend_policy(&self, owner: Address, spender: Address) -> Option<SpendPolicy> { self.spend_policies.get((owner, spender)) }
+
+        #[ink(message)]
+        pub fn policy_spent(&self, owner: Address, spender: Address) -> U256 { self.policy_spent.get((owner, spender)).unwrap_or_default() }
+
+        /// Constrains `spender`'s use of the caller's allowance, restarting the cumulative spend count.
+        #[ink(message)]
+        pub fn set_spend_policy(&mut self, spender: Address, policy: SpendPolicy) -> Result<()> {
+            let owner = self.env().caller();
+            self.spend_policies.insert((owner, spender), &policy);
+            self.policy_spent.remove((owner, spender));
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn spend_destination(&self, owner: Address, spender: Address) -> Option<Address> { self.spend_destinations.get((owner, spender)) }
+
+        /// Restricts `spender`'s `transfer_from` on the caller's tokens to sending only to `allowed_to`.
+        #[ink(message)]
+        pub fn set_spend_destination(&mut self, spender: Address, allowed_to: Address) -> Result<()> {
+            let owner = self.env().caller();
+            self.spend_destinations.insert((owner, spender), &allowed_to);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn spend_rate_limit(&self, spender: Address) -> Option<(U256, u64)> { self.spend_rate_limit.get(spender) }
+
+        /// Caps how much `spender` may move via `transfer_from` across all owners, per `window` seconds.
+        #[ink(message)]
+        pub fn set_spend_rate_limit(&mut self, spender: Address, amount: U256, window: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.spend_rate_limit.insert(spender, &(amount, window));
+            self.spend_rate_usage.remove(spender);
+            Ok(())
+        }
+
+        fn spend_under_rate_limit(&mut self, spender: &Address, value: U256) -> Result<()> {
+            let Some((amount, window)) = self.spend_rate_limit.get(spender) else { return Ok(()) };
+            let now = self.env().block_timestamp();
+            let (used, window_start) = match self.spend_rate_usage.get(spender) {
+                Some((used, start)) if now < start.saturating_add(window) => (used, start),
+                _ => (U256::zero(), now),
+            };
+            let used = used.checked_add(value).ok_or(Error::Overflow)?;
+            if used > amount { return Err(Error::SpendRateExceeded) }
+            self.spend_rate_usage.insert(spender, &(used, window_start));
+            Ok(())
+        }
+
+        fn spend_under_policy(&mut self, owner: &Address, spender: &Address, value: U256) -> Result<()> {
+            let Some(policy) = self.spend_policies.get((owner, spender)) else { return Ok(()) };
+            if policy.expires_at != 0 && self.env().block_timestamp() >= policy.expires_at { return Err(Error::PolicyExpired) }
+            if !policy.max_per_tx.is_zero() && value > policy.max_per_tx { return Err(Error::PolicyPerTxExceeded) }
+            let spent = self.policy_spent(*owner, *spender).checked_add(value).ok_or(Error::Overflow)?;
+            if !policy.max_total.is_zero() && spent > policy.max_total { return Err(Error::PolicyTotalExceeded) }
+            self.policy_spent.insert((owner, spender), &spent);
+            Ok(())
+        }
+
+        /// Unfilled remainder of `order_id` placed by `maker` for settlement by `spender`, once it has been opened.
+        #[ink(message)]
+        pub fn open_fill(&self, maker: Address, spender: Address, order_id: u64) -> Option<U256> { self.open_fills.get((maker, spender, order_id)) }
+
+        /// Settles `fill` of the `total`-sized order `order_id` from `maker` to `taker` out of the maker's allowance to the caller.
+        /// The first fill opens the order; later fills draw down the recorded remainder.
+        /// Remainders are keyed by `(maker, caller, order_id)` rather than `(maker, taker)`: a `(maker, taker)`
+        /// key lets any spender of the maker consume or reset another spender's order, and leaves no way to
+        /// place a second order once the first is fully filled.
+        #[ink(message)]
+        pub fn fill_order(&mut self, maker: Address, taker: Address, order_id: u64, total: U256, fill: U256) -> Result<()> {
+            let key = (maker, self.env().caller(), order_id);
+            let remaining = self.open_fills.get(key).unwrap_or(total);
+            if fill > remaining { return Err(Error::Overfill) }
+            self.transfer_from(maker, taker, fill)?;
+            self.open_fills.insert(key, &(remaining - fill));
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn airdrop_allocation(&self, account: Address) -> U256 { self.airdrop_allocations.get(account).unwrap_or_default() }
+
+        #[ink(message)]
+        pub fn airdrop_budget(&self) -> U256 { self.airdrop_budget }
+
+        #[ink(message)]
+        pub fn airdrop_claimed(&self) -> U256 { self.airdrop_claimed }
+
+        #[ink(message)]
+        pub fn claim_deadline(&self) -> u64 { self.claim_deadline }
+
+        /// Sets how much `account` may claim; the airdrop budget tracks the sum of all allocations.
+        #[ink(message)]
+        pub fn set_airdrop_allocation(&mut self, account: Address, amount: U256) -> Result<()> {
+            self.ensure_owner()?;
+            let previous = self.airdrop_allocation(account);
+            self.airdrop_budget = (self.airdrop_budget - previous).checked_add(amount).ok_or(Error::Overflow)?;
+            self.airdrop_allocations.insert(account, &amount);
+            Ok(())
+        }
+
+        /// Sets the timestamp after which claims close; zero means claims never close.
+        #[ink(message)]
+        pub fn set_claim_deadline(&mut self, deadline: u64) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.claim_deadline = deadline;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn claim(&mut self) -> Result<()> {
+            if self.claim_window_closed() { return Err(Error::ClaimWindowClosed) }
+            let caller = self.env().caller();
+            let amount = self.airdrop_allocation(caller);
+            if amount.is_zero() { return Err(Error::NothingToClaim) }
+            self.mint_to(&caller, amount)?;
+            self.airdrop_allocations.remove(caller);
+            self.airdrop_claimed += amount;
+            Ok(())
+        }
+
+        /// Mints the unclaimed remainder of the airdrop budget to `to` once the claim window has closed, closing it for good.
+        #[ink(message)]
+        pub fn sweep_unclaimed(&mut self, to: Address) -> Result<()> {
+            self.ensure_owner()?;
+            if !self.claim_window_closed() { return Err(Error::ClaimWindowOpen) }
+            let remaining = self.airdrop_budget.saturating_sub(self.airdrop_claimed);
+            if remaining.is_zero() { return Err(Error::NothingToClaim) }
+            self.mint_to(&to, remaining)?;
+            self.airdrop_claimed = self.airdrop_budget;
+            self.airdrop_swept = true;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn scale_factor(&self) -> (U256, U256) { (self.scale_numerator, self.scale_denominator) }
+
+        /// `decimals` shifted by the whole powers of ten the redenomination scale has applied, so a 10x split
+        /// displays with one more decimal.
+        #[ink(message)]
+        pub fn effective_decimals(&self) -> u8 {
+            let (larger, smaller) = if self.scale_numerator >= self.scale_denominator {
+                (self.scale_numerator, self.scale_denominator)
+            } else {
+                (self.scale_denominator, self.scale_numerator)
+            };
+            let mut ratio = larger / smaller;
+            let mut shift = 0u8;
+            while ratio >= U256::from(10u8) {
+                ratio /= U256::from(10u8);
+                shift = shift.saturating_add(1);
+            }
+            if self.scale_numerator >= self.scale_denominator {
+                self.decimals.saturating_add(shift)
+            } else {
+                self.decimals.saturating_sub(shift)
+            }
+        }
+
+        /// Splits (`multiply`) or merges every balance and the total supply by `factor` in one step.
+        /// A merge floors each balance separately but the supply only once, so `total_supply` can exceed the
+        /// sum of balances by the rounding dust.
+        #[ink(message)]
+        pub fn redenominate(&mut self, factor: u32, multiply: bool) -> Result<()> {
+            self.ensure_owner()?;
+            if factor == 0 { return Err(Error::InvalidFactor) }
+            // Escrow is tracked in raw units, so rescaling the contract's own balance would strand it.
+            if !self.total_staked.is_zero() { return Err(Error::EscrowOutstanding) }
+            if !self.pending_liquidity.is_zero() { return Err(Error::EscrowOutstanding) }
+            if !self.total_vesting.is_zero() { return Err(Error::EscrowOutstanding) }
+            let factor_u256 = U256::from(factor);
+            if multiply {
+                self.total_supply = self.total_supply.checked_mul(factor_u256).ok_or(Error::Overflow)?;
+                self.scale_numerator = self.scale_numerator.checked_mul(factor_u256).ok_or(Error::Overflow)?;
+            } else {
+                self.total_supply /= factor_u256;
+                self.scale_denominator = self.scale_denominator.checked_mul(factor_u256).ok_or(Error::Overflow)?;
+            }
+            let divisor = gcd(self.scale_numerator, self.scale_denominator);
+            self.scale_numerator /= divisor;
+            self.scale_denominator /= divisor;
+            self.env().emit_event(Redenomination { factor, multiply });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn redemption_rate(&self) -> U256 { self.redemption_rate }
+
+        #[ink(message)]
+        pub fn set_redemption_rate(&mut self, rate: U256) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.redemption_rate = rate;
+            Ok(())
+        }
+
+        /// Native reserve backing each token unit, scaled by `RATE_PRECISION`; zero while there is no supply.
+        #[ink(message)]
+        pub fn implied_price(&self) -> U256 {
+            if self.total_supply.is_zero() { return U256::zero() }
+            self.env().balance().saturating_mul(U256::from(RATE_PRECISION)) / self.total_supply
+        }
+
+        /// Burns `amount` of the caller's tokens for native currency at `redemption_rate`, failing if the payout is below `min_out`.
+        #[ink(message)]
+        pub fn redeem(&mut self, amount: U256, min_out: U256) -> Result<()> {
+            let caller = self.env().caller();
+            let payout = amount.checked_mul(self.redemption_rate).ok_or(Error::Overflow)? / U256::from(RATE_PRECISION);
+            if payout < min_out { return Err(Error::SlippageExceeded) }
+            self.burn_from_account(&caller, amount)?;
+            self.env().transfer(caller, payout).map_err(|_| Error::NativeTransferFailed)
+        }
+
+        /// Tokens in circulation backed 1:1 by deposited native currency.
+        #[ink(message)]
+        pub fn wrapped_supply(&self) -> U256 { self.wrapped_supply }
+
+        /// Native currency `account` has deposited and not yet withdrawn.
+        #[ink(message)]
+        pub fn deposited_of(&self, account: Address) -> U256 { self.native_deposits.get(account).unwrap_or_default() }
+
+        /// Wraps the attached native value, minting the same amount of tokens to the caller.
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let value = self.env().transferred_value();
+            self.mint_to(&caller, value)?;
+            let deposited = self.deposited_of(caller).checked_add(value).ok_or(Error::Overflow)?;
+            self.native_deposits.insert(caller, &deposited);
+            self.wrapped_supply = self.wrapped_supply.checked_add(value).ok_or(Error::Overflow)?;
+            Ok(())
+        }
+
+        /// Burns `amount` of the caller's tokens and returns the same amount of native currency. Only the caller's own
+        /// deposits are redeemable, so unbacked supply (the initial mint, rewards, airdrops) can never drain them.
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: U256) -> Result<()> {
+            let caller = self.env().caller();
+            if self.balance_of_impl(&caller) < amount { return Err(Error::InsufficientBalance) }
+            let deposited = self.deposited_of(caller);
+            if amount > deposited { return Err(Error::ExceedsDeposit) }
+            self.burn_from_account(&caller, amount)?;
+            self.native_deposits.insert(caller, &(deposited - amount));
+            self.wrapped_supply -= amount;
+            self.env().transfer(caller, amount).map_err(|_| Error::NativeTransferFailed)
+        }
+
+        #[ink(message)]
+        pub fn stake_of(&self, account: Address) -> Stake { self.settled_stake(&account) }
+
+        #[ink(message)]
+        pub fn total_staked(&self) -> U256 { self.total_staked }
+
+        #[ink(message)]
+        pub fn reward_rate_per_sec(&self) -> U256 { self.reward_rate_per_sec }
+
+        /// Reward minted per staked token per second, scaled by `RATE_PRECISION`.
+        #[ink(message)]
+        pub fn set_reward_rate_per_sec(&mut self, rate: U256) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.reward_rate_per_sec = rate;
+            Ok(())
+        }
+
+        /// Annualised staking reward in basis points of the staked amount; zero while nothing is staked.
+        #[ink(message)]
+        pub fn current_apr(&self) -> U256 {
+            if self.total_staked.is_zero() { return U256::zero() }
+            self.reward_rate_per_sec.saturating_mul(U256::from(SECONDS_PER_YEAR)).saturating_mul(U256::from(MAX_BPS)) / U256::from(RATE_PRECISION)
+        }
+
+        /// Every vesting schedule held for `beneficiary`, indexed as `releasable` and `release` expect.
+        #[ink(message)]
+        pub fn vesting_schedules(&self, beneficiary: Address) -> Vec<VestingSchedule> { self.vesting.get(beneficiary).unwrap_or_default() }
+
+        /// Escrows `amount` of the owner's tokens for `beneficiary` under a new linear schedule with a cliff.
+        #[ink(message)]
+        pub fn create_vesting(&mut self, beneficiary: Address, amount: U256, start: u64, duration: u64, cliff: u64) -> Result<()> {
+            self.ensure_owner()?;
+            if duration == 0 || cliff > duration { return Err(Error::InvalidWindow) }
+            let caller = self.env().caller();
+            self.move_tokens(&caller, &self.env().address(), amount)?;
+            let mut schedules = self.vesting_schedules(beneficiary);
+            schedules.push(VestingSchedule { total: amount, released: U256::zero(), start, duration, cliff });
+            self.vesting.insert(beneficiary, &schedules);
+            self.total_vesting += amount;
+            Ok(())
+        }
+
+        /// Vested but not yet released under schedule `index`; zero before the cliff, then linear from `start`.
+        #[ink(message)]
+        pub fn releasable(&self, beneficiary: Address, index: u32) -> U256 {
+            self.vesting_schedules(beneficiary).get(index as usize).map_or(U256::zero(), |schedule| self.vested(schedule) - schedule.released)
+        }
+
+        /// Releasable amount summed over all of `beneficiary`'s schedules.
+        #[ink(message)]
+        pub fn total_releasable(&self, beneficiary: Address) -> U256 {
+            self.vesting_schedules(beneficiary).iter().fold(U256::zero(), |sum, schedule| sum + self.vested(schedule) - schedule.released)
+        }
+
+        #[ink(message)]
+        pub fn release(&mut self, index: u32) -> Result<()> {
+            let caller = self.env().caller();
+            let mut schedules = self.vesting_schedules(caller);
+            let schedule = schedules.get_mut(index as usize).ok_or(Error::NothingToClaim)?;
+            let amount = self.vested(schedule) - schedule.released;
+            if amount.is_zero() { return Err(Error::NothingToClaim) }
+            schedule.released += amount;
+            self.vesting.insert(caller, &schedules);
+            self.total_vesting -= amount;
+            self.move_tokens(&self.env().address(), &caller, amount)
+        }
+
+        /// Claims everything due across all of the caller's schedules.
+        #[ink(message)]
+        pub fn release_all(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let mut schedules = self.vesting_schedules(caller);
+            let mut amount = U256::zero();
+            for schedule in schedules.iter_mut() {
+                let due = self.vested(schedule) - schedule.released;
+                schedule.released += due;
+                amount += due;
+            }
+            if amount.is_zero() { return Err(Error::NothingToClaim) }
+            self.vesting.insert(caller, &schedules);
+            self.total_vesting -= amount;
+            self.move_tokens(&self.env().address(), &caller, amount)
+        }
+
+        fn vested(&self, schedule: &VestingSchedule) -> U256 {
+            let now = self.env().block_timestamp();
+            if now < schedule.start.saturating_add(schedule.cliff) { return U256::zero() }
+            let elapsed = now - schedule.start;
+            if elapsed >= schedule.duration { return schedule.total }
+            schedule.total * U256::from(elapsed) / U256::from(schedule.duration)
+        }
+
+        /// Escrows `amount` of the caller's tokens in the contract so they accrue staking rewards.
+        #[ink(message)]
+        pub fn stake(&mut self, amount: U256) -> Result<()> {
+            let caller = self.env().caller();
+            let mut stake = self.settled_stake(&caller);
+            self.move_tokens(&caller, &self.env().address(), amount)?;
+            stake.amount += amount;
+            self.total_staked += amount;
+            self.stakes.insert(caller, &stake);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn unstake(&mut self, amount: U256) -> Result<()> {
+            let caller = self.env().caller();
+            let mut stake = self.settled_stake(&caller);
+            if stake.amount < amount { return Err(Error::InsufficientStake) }
+            self.move_tokens(&self.env().address(), &caller, amount)?;
+            stake.amount -= amount;
+            self.total_staked -= amount;
+            self.stakes.insert(caller, &stake);
+            Ok(())
+        }
+
+        /// Mints every reward the caller has accrued so far.
+        #[ink(message)]
+        pub fn claim_rewards(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let mut stake = self.settled_stake(&caller);
+            if stake.accrued.is_zero() { return Err(Error::NothingToClaim) }
+            self.mint_to(&caller, stake.accrued)?;
+            stake.accrued = U256::zero();
+            self.stakes.insert(caller, &stake);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn recovery_delay(&self) -> u64 { self.recovery_delay }
+
+        #[ink(message)]
+        pub fn set_recovery_delay(&mut self, delay: u64) -> Result<()> {
+            self.ensure_config_owner()?;
+            self.recovery_delay = delay;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn pending_recovery(&self) -> Option<PendingRecovery> { self.pending_recovery.clone() }
+
+        /// Schedules moving `value` from `from` to `to`, executable once `recovery_delay` has elapsed.
+        /// A new proposal replaces any pending one.
+        #[ink(message)]
+        pub fn propose_recovery(&mut self, from: Address, to: Address, value: U256) -> Result<()> {
+            self.ensure_owner()?;
+            let eta = self.env().block_timestamp().saturating_add(self.recovery_delay);
+            self.pending_recovery = Some(PendingRecovery { from, to, value, eta });
+            self.env().emit_event(RecoveryProposed { from, to, value, eta });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn execute_recovery(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            let recovery = self.pending_recovery.clone().ok_or(Error::NoPendingRecovery)?;
+            if self.env().block_timestamp() < recovery.eta { return Err(Error::RecoveryNotReady) }
+            self.move_tokens(&recovery.from, &recovery.to, recovery.value)?;
+            self.pending_recovery = None;
+            self.env().emit_event(RecoveryExecuted { from: recovery.from, to: recovery.to, value: recovery.value });
+            Ok(())
+        }
+
+        /// Burns whatever balance has accumulated at the zero address and returns the amount burned.
+        #[ink(message)]
+        pub fn sync_burn_address(&mut self) -> U256 {
+            let zero = Address::from([0u8; 20]);
+            let burned = self.balance_of_impl(&zero);
+            if burned.is_zero() { return burned }
+            self.set_balance(&zero, U256::zero());
+            self.total_supply -= burned;
+            self.total_burned += burned;
+            self.env().emit_event(Transfer { from: Some(zero), to: None, value: burned });
+            burned
+        }
+
+        #[ink(message)]
+        pub fn burn(&mut self, value: U256) -> Result<()> {
+            let caller = self.env().caller();
+            self.burn_from_account(&caller, value)
+        }
+
+        #[ink(message)]
+        pub fn burn_from(&mut self, account: Address, value: U256) -> Result<()> {
+            let caller = self.env().caller();
+            let allowance = self.allowance_impl(&account, &caller);
+            if allowance < value { return Err(Error::InsufficientAllowance) }
+            self.burn_from_account(&account, value)?;
+            self.set_allowance(&account, &caller, allowance - value);
+            Ok(())
+        }
+
+        /// Cumulative amount removed from supply by every burn path, including the zero-address sink.
+        #[ink(message)]
+        pub fn burned_total(&self) -> U256 { self.total_burned }
+
+        fn settled_stake(&self, account: &Address) -> Stake {
+            let mut stake = self.stakes.get(account).unwrap_or_default();
+            let now = self.env().block_timestamp();
+            let elapsed = U256::from(now.saturating_sub(stake.since));
+            stake.accrued += stake.amount.saturating_mul(self.reward_rate_per_sec).saturating_mul(elapsed) / U256::from(RATE_PRECISION);
+            stake.since = now;
+            stake
+        }
+
+        /// Moves tokens between accounts without fees or transfer restrictions, for internal escrow.
+        fn move_tokens(&mut self, from: &Address, to: &Address, value: U256) -> Result<()> {
+            let from_balance = self.balance_of_impl(from);
+            if from_balance < value { return Err(Error::InsufficientBalance) }
+            self.set_balance(from, from_balance - value);
+            self.credit(to, value)?;
+            self.env().emit_event(Transfer { from: Some(*from), to: Some(*to), value });
+            Ok(())
+        }
+
+        fn burn_from_account(&mut self, from: &Address, value: U256) -> Result<()> {
+            let from_balance = self.balance_of_impl(from);
+            if from_balance < value { return Err(Error::InsufficientBalance) }
+            self.set_balance(from, from_balance - value);
+            self.total_supply -= value;
+            self.total_burned += value;
+            self.env().emit_event(Transfer { from: Some(*from), to: None, value });
+            Ok(())
+        }
+
+        /// A swept airdrop stays closed even if the deadline is later extended.
+        fn claim_window_closed(&self) -> bool {
+            self.airdrop_swept || (self.claim_deadline != 0 && self.env().block_timestamp() > self.claim_deadline)
+        }
+
+        fn mint_to(&mut self, to: &Address, value: U256) -> Result<()> {
+            if self.mints_paused { return Err(Error::MintsPaused) }
+            if value > self.remaining_mintable() { return Err(Error::CapExceeded) }
+            self.total_supply = self.total_supply.checked_add(value).ok_or(Error::Overflow)?;
+            let to_balance = self.balance_of_impl(to);
+            self.set_balance(to, to_balance + value);
+            self.acquired_at.insert(to, &self.env().block_timestamp());
+            self.env().emit_event(Transfer { from: None, to: Some(*to), value });
+            Ok(())
+        }
+
+        fn keccak(&self, input: &[u8]) -> [u8; 32] { self.env().hash_bytes::<Keccak256>(input) }
+
+        fn hash_words(&self, words: &[[u8; 32]]) -> [u8; 32] { self.keccak(&words.concat()) }
+
+        /// Recovers the Ethereum address that signed the EIP-712 digest of `struct_hash` under this token's domain.
+        fn recover_typed_signer(&self, struct_hash: &[u8; 32], signature: &[u8; 65]) -> Result<Address> {
+            let mut message = [0u8; 66];
+            message[..2].copy_from_slice(&[0x19, 0x01]);
+            message[2..34].copy_from_slice(&self.domain_separator());
+            message[34..].copy_from_slice(struct_hash);
+            let digest = self.keccak(&message);
+            let public_key = self.env().ecdsa_recover(signature, &digest).map_err(|_| Error::InvalidSignature)?;
+            let address = self.env().ecdsa_to_eth_address(&public_key).map_err(|_| Error::InvalidSignature)?;
+            Ok(Address::from(address))
+        }
+
+        fn capped_allowance(&self, owner: &Address, value: U256) -> U256 {
+            if !self.cap_allowance_to_balance { return value }
+            value.min(self.balance_of_impl(owner))
+        }
+
+        /// Writes an allowance and keeps the spender's `total_allowance_granted` aggregate in step.
+        fn set_allowance(&mut self, owner: &Address, spender: &Address, value: U256) {
+            let previous = self.allowances.get((owner, spender)).unwrap_or_default();
+            let granted = self.total_granted_to(*spender).saturating_sub(previous).saturating_add(value);
+            self.total_allowance_granted.insert(spender, &granted);
+            self.allowances.insert((owner, spender), &value);
+            self.allowance_epochs.insert((owner, spender), &self.approval_epoch);
+        }
+
+        fn approve_impl(&mut self, owner: &Address, spender: &Address, value: U256) {
+            self.set_allowance(owner, spender, value);
+            if !value.is_zero() {
+                self.approved_at.insert((owner, spender), &self.env().block_timestamp());
+                let mut spenders = self.approved_spenders(*owner);
+                if !spenders.contains(spender) {
+                    spenders.push(*spender);
+                    self.approved_spenders.insert(owner, &spenders);
+                }
+            }
+            self.env().emit_event(Approval { owner: *owner, spender: *spender, value });
+        }
+
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner { return Err(Error::NotOwner) }
+            Ok(())
+        }
+
+        fn ensure_minter(&self) -> Result<()> {
+            if !self.is_minter(self.env().caller()) { return Err(Error::NotMinter) }
+            Ok(())
+        }
+
+        fn ensure_config_owner(&self) -> Result<()> {
+            self.ensure_owner()?;
+            if self.config_locked { return Err(Error::ConfigLocked) }
+            Ok(())
+        }
+
+        fn transfer_fee(&self, from: &Address, to: &Address, value: U256) -> U256 {
+            let fee_bps = self.discounted_fee_bps(from, self.fee_bps_for(value));
+            if fee_bps == 0 || self.is_fee_exempt(*from) || self.is_fee_exempt(*to) || self.in_fee_holiday() { return U256::zero() }
+            // Routed taxation never touches the owner's own transfers.
+            if self.tax_sink.is_some() && (*from == self.owner || *to == self.owner) { return U256::zero() }
+            bps_of(value, fee_bps)
+        }
+
+        fn record_activity(&mut self, account: &Address, counterparty: &Address, value: U256) {
+            let mut recent = self.recent_activity(*account);
+            if recent.len() == RECENT_TRANSFERS_LIMIT { recent.remove(0); }
+            recent.push((*counterparty, value, self.env().block_timestamp()));
+            self.recent_transfers.insert(account, &recent);
+        }
+
+        /// The owner is never held; everyone else must wait `min_hold_secs` after their latest receipt.
+        fn in_hold_period(&self, account: &Address) -> bool {
+            if self.min_hold_secs == 0 || *account == self.owner { return false }
+            let acquired_at = self.acquired_at.get(account).unwrap_or_default();
+            self.env().block_timestamp().saturating_sub(acquired_at) < self.min_hold_secs
+        }
+
+        /// Mints the referral bonus for a transfer, except on self-transfers or payments to the referrer itself.
+        fn reward_referrer(&mut self, from: &Address, to: &Address, value: U256) -> Result<()> {
+            if self.referral_bps == 0 || self.mints_paused || from == to { return Ok(()) }
+            let Some(referrer) = self.referrers.get(from) else { return Ok(()) };
+            if referrer == *to { return Ok(()) }
+            // Clamped to the cap's headroom so a capped supply never blocks ordinary transfers.
+            let bonus = bps_of(value, self.referral_bps).min(self.remaining_mintable());
+            if bonus.is_zero() { return Ok(()) }
+            self.mint_to(&referrer, bonus)
+        }
+
+        fn collect_fee(&mut self, from: &Address, collector: &Address, fee: U256) -> Result<()> {
+            if fee.is_zero() { return Ok(()) }
+            self.credit(collector, fee)?;
+            self.env().emit_event(Transfer { from: Some(*from), to: Some(*collector), value: fee });
+            Ok(())
+        }
+
+        fn transfer_reflection(&self, from: &Address, to: &Address, value: U256) -> U256 {
+            if self.reflection_bps == 0 || self.is_fee_exempt(*from) || self.is_fee_exempt(*to) { return U256::zero() }
+            bps_of(value, self.reflection_bps)
+        }
+
+        /// Raises the per-token accumulator so `reflection` is shared across the eligible supply.
+        /// With no eligible holders the reflection is credited to `recipient` instead.
+        fn distribute_reflection(&mut self, recipient: &Address, reflection: U256) -> Result<()> {
+            let mut excluded: Vec<Address> = vec![self.owner, self.fee_collector, self.env().address()];
+            excluded.extend(self.reflection_excluded.iter().copied());
+            excluded.sort();
+            excluded.dedup();
+            let excluded_supply = excluded.iter().fold(U256::zero(), |sum, account| sum + self.balance_of_impl(account));
+            let eligible = self.total_supply.saturating_sub(reflection).saturating_sub(excluded_supply);
+            if eligible.is_zero() { return self.credit(recipient, reflection) }
+            self.reflection_per_token += reflection * U256::from(RATE_PRECISION) / eligible;
+            Ok(())
+        }
+
+        fn credit(&mut self, account: &Address, value: U256) -> Result<()> {
+            let balance = self.balance_of_impl(account).checked_add(value).ok_or(Error::Overflow)?;
+            self.set_balance(account, balance);
+            Ok(())
+        }
+
+        fn ensure_code_hash_allowed(&self, to: &Address) -> Result<()> {
+            if !self.env().is_contract(to) { return Ok(()) }
+            match self.env().code_hash(to) {
+                Ok(code_hash) if self.is_code_hash_blocked(code_hash.0) => Err(Error::BlockedCodeHash),
+                _ => Ok(()),
+            }
+        }
+
+        /// The sender's transfer count in this block including the next one, or `None` when uncapped.
+        fn next_block_transfer_count(&self, from: &Address) -> Result<Option<u32>> {
+            if self.max_transfers_per_block == 0 || *from == self.owner { return Ok(None) }
+            let block = self.env().block_number();
+            let count = match self.block_transfers.get(from) {
+                Some((last_block, count)) if last_block == block => count + 1,
+                _ => 1,
+            };
+            if count > self.max_transfers_per_block { return Err(Error::TooManyTransfers) }
+            Ok(Some(count))
+        }
+
+        fn record_block_transfer(&mut self, from: &Address) -> Result<()> {
+            if let Some(count) = self.next_block_transfer_count(from)? {
+                self.block_transfers.insert(from, &(self.env().block_number(), count));
+            }
+            Ok(())
+        }
+
+        /// Runs every restriction a transfer is subject to, returning the sender's balance.
+        fn check_transfer(&self, from: &Address, to: &Address, value: U256) -> Result<U256> {
+            if self.transfers_paused { return Err(Error::TransfersPaused) }
+            if !value.is_zero() && value < self.min_transfer { return Err(Error::BelowMinTransfer) }
+            let from_balance = self.balance_of_impl(from);
+            if from_balance < value { return Err(Error::InsufficientBalance) }
+            if self.is_blacklisted(*from) || self.is_blacklisted(*to) { return Err(Error::Blacklisted) }
+            if self.is_frozen(*from) { return Err(Error::AccountFrozen) }
+            if self.in_hold_period(from) { return Err(Error::HoldPeriodActive) }
+            if self.whitelist_mode && !(self.is_whitelisted(*from) && self.is_whitelisted(*to)) { return Err(Error::NotWhitelisted) }
+            if self.reject_self_transfers && *to == self.env().address() { return Err(Error::SelfContractTransfer) }
+            self.ensure_code_hash_allowed(to)?;
+            self.next_block_transfer_count(from)?;
+            Ok(from_balance)
+        }
+
+        /// Folds `received` tokens at `last_price` into `to`'s balance-weighted average cost.
+        fn update_cost_basis(&mut self, to: &Address, received: U256) {
+            let held = self.balance_of_impl(to);
+            let Some(total) = held.checked_add(received).filter(|total| !total.is_zero()) else { return };
+            let basis = self.cost_basis_of(*to).saturating_mul(held).saturating_add(self.last_price.saturating_mul(received)) / total;
+            self.cost_basis.insert(to, &basis);
+        }
+
+        fn transfer_from_to(&mut self, from: &Address, to: &Address, value: U256) -> Result<()> {
+            let from_balance = self.check_transfer(from, to, value)?;
+            self.record_block_transfer(from)?;
+            let fee = self.transfer_fee(from, to, value);
+            let reflection = self.transfer_reflection(from, to, value - fee);
+            self.set_balance(from, from_balance - value);
+            self.last_transfer_at.insert(from, &self.env().block_timestamp());
+            let liquidity = bps_of(fee, self.liquidity_bps);
+            if !liquidity.is_zero() {
+                let this = self.env().address();
+                self.collect_fee(from, &this, liquidity)?;
+                self.pending_liquidity += liquidity;
+            }
+            let collected = fee - liquidity;
+            let sender = match self.tax_sink {
+                _ if collected.is_zero() => *from,
+                Some(sink) => {
+                    self.credit(&sink, collected)?;
+                    self.env().emit_event(Transfer { from: Some(*from), to: Some(sink), value: value - liquidity - reflection });
+                    sink
+                }
+                None => {
+                    match self.fee_split {
+                        Some((collector_a, collector_b, split_bps)) => {
+                            let share_b = bps_of(collected, MAX_BPS - split_bps);
+                            self.collect_fee(from, &collector_a, collected - share_b)?;
+                            self.collect_fee(from, &collector_b, share_b)?;
+                        }
+                        None => {
+                            let collector = self.fee_collector;
+                            self.collect_fee(from, &collector, collected)?;
+                        }
+                    }
+                    *from
+                }
+            };
+            let received = value - fee - reflection;
+            self.update_cost_basis(to, received);
+            self.credit(to, received)?;
+            if *to == self.env().address() { self.received_by_contract += received }
+            self.acquired_at.insert(to, &self.env().block_timestamp());
+            self.env().emit_event(Transfer { from: Some(sender), to: Some(*to), value: received });
+            if self.indexed_transfers {
+                self.env().emit_event(TransferIndexed { from: Some(sender), to: Some(*to), magnitude: magnitude_of(received), value: received });
+            }
+            self.reward_referrer(from, to, value)?;
+            self.record_activity(from, to, value);
+            if from != to { self.record_activity(to, from, value); }
+            self.transfer_seq += 1;
+            self.env().emit_event(SequencedTransfer { seq: self.transfer_seq, from: *from, to: *to, value });
+            if !reflection.is_zero() {
+                self.distribute_reflection(to, reflection)?;
+                self.env().emit_event(Reflected { from: *from, value: reflection });
+            }
+            if *from != self.owner && self.would_trip_breaker(value) {
+                self.transfers_paused = true;
+                self.env().emit_event(CircuitBreakerTripped { from: *from, value });
+            }
+            self.notify_registry(from, to, value);
+            Ok(())
+        }
+
+        /// Best-effort `notify_transfer` to the configured registry; a failing registry never blocks the transfer.
+        fn notify_registry(&self, from: &Address, to: &Address, value: U256) {
+            let Some(registry) = self.registry else { return };
+            if !self.env().is_contract(&registry) { return }
+            let _ = build_call::<Environment>()
+                .call(registry)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("notify_transfer")))
+                        .push_arg(self.env().address())
+                        .push_arg(*from)
+                        .push_arg(*to)
+                        .push_arg(value),
+                )
+                .ref_time_limit(CALLBACK_REF_TIME_LIMIT)
+                .proof_size_limit(CALLBACK_PROOF_SIZE_LIMIT)
+                .storage_deposit_limit(U256::from(CALLBACK_STORAGE_DEPOSIT_LIMIT))
+                .returns::<()>()
+                .try_invoke();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "e2e-tests"))]
+mod e2e_tests {
+    use super::erc20::{Erc20, Erc20Ref, Error};
+    use ink::U256;
+    use ink_e2e::ContractsBackend;
+    use mock_receiver::mock_receiver::{MockReceiver, MockReceiverRef};
+
+    type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[ink_e2e::test]
+    async fn pay_notifies_contract_recipient<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+        let mut constructor = Erc20Ref::new(U256::from(1000));
+        let token = client.instantiate("erc20", &ink_e2e::alice(), &mut constructor).submit().await.expect("erc20 instantiate failed");
+        let mut token_call = token.call_builder::<Erc20>();
+        let mut receiver_constructor = MockReceiverRef::new(false);
+        let receiver = client.instantiate("mock_receiver", &ink_e2e::alice(), &mut receiver_constructor).submit().await.expect("receiver instantiate failed");
+        let mut receiver_call = receiver.call_builder::<MockReceiver>();
+
+        let alice = client.call(&ink_e2e::alice(), &token_call.owner()).dry_run().await?.return_value();
+        let pay = token_call.pay(receiver.addr, U256::from(100), 7);
+        let result = client.call(&ink_e2e::alice(), &pay).submit().await.expect("pay failed");
+        assert_eq!(result.return_value(), Ok(()));
+
+        let last_payment = client.call(&ink_e2e::alice(), &receiver_call.last_payment()).dry_run().await?.return_value();
+        assert_eq!(last_payment, Some((alice, U256::from(100), 7)));
+        let balance = client.call(&ink_e2e::alice(), &token_call.balance_of(receiver.addr)).dry_run().await?.return_value();
+        assert_eq!(balance, U256::from(100));
+        Ok(())
+    }
+
+    #[ink_e2e::test]
+    async fn pay_settles_when_callback_fails<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+        let mut constructor = Erc20Ref::new(U256::from(1000));
+        let token = client.instantiate("erc20", &ink_e2e::alice(), &mut constructor).submit().await.expect("erc20 instantiate failed");
+        let mut token_call = token.call_builder::<Erc20>();
+        let mut receiver_constructor = MockReceiverRef::new(true);
+        let receiver = client.instantiate("mock_receiver", &ink_e2e::alice(), &mut receiver_constructor).submit().await.expect("receiver instantiate failed");
+        let mut receiver_call = receiver.call_builder::<MockReceiver>();
+
+        let pay = token_call.pay(receiver.addr, U256::from(100), 8);
+        let result = client.call(&ink_e2e::alice(), &pay).submit().await.expect("pay failed");
+        assert_eq!(result.return_value(), Ok(()));
+
+        let last_payment = client.call(&ink_e2e::alice(), &receiver_call.last_payment()).dry_run().await?.return_value();
+        assert_eq!(last_payment, None);
+        let balance = client.call(&ink_e2e::alice(), &token_call.balance_of(receiver.addr)).dry_run().await?.return_value();
+        assert_eq!(balance, U256::from(100));
+        Ok(())
+    }
+
+    #[ink_e2e::test]
+    async fn transfer_notifies_registry<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+        let mut constructor = Erc20Ref::new(U256::from(1000));
+        let token = client.instantiate("erc20", &ink_e2e::alice(), &mut constructor).submit().await.expect("erc20 instantiate failed");
+        let mut token_call = token.call_builder::<Erc20>();
+        let mut registry_constructor = MockReceiverRef::new(false);
+        let registry = client.instantiate("mock_receiver", &ink_e2e::alice(), &mut registry_constructor).submit().await.expect("registry instantiate failed");
+        let mut registry_call = registry.call_builder::<MockReceiver>();
+
+        let alice = client.call(&ink_e2e::alice(), &token_call.owner()).dry_run().await?.return_value();
+        let recipient = ink::primitives::Address::from([7u8; 20]);
+        client.call(&ink_e2e::alice(), &token_call.set_registry(Some(registry.addr))).submit().await.expect("set_registry failed");
+        let transfer = token_call.transfer(recipient, U256::from(25));
+        client.call(&ink_e2e::alice(), &transfer).submit().await.expect("transfer failed");
+
+        let notification = client.call(&ink_e2e::alice(), &registry_call.last_notification()).dry_run().await?.return_value();
+        assert_eq!(notification, Some((token.addr, alice, recipient, U256::from(25))));
+        Ok(())
+    }
+
+    #[ink_e2e::test]
+    async fn failing_registry_does_not_block_transfer<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+        let mut constructor = Erc20Ref::new(U256::from(1000));
+        let token = client.instantiate("erc20", &ink_e2e::alice(), &mut constructor).submit().await.expect("erc20 instantiate failed");
+        let mut token_call = token.call_builder::<Erc20>();
+        let mut registry_constructor = MockReceiverRef::new(true);
+        let registry = client.instantiate("mock_receiver", &ink_e2e::alice(), &mut registry_constructor).submit().await.expect("registry instantiate failed");
+
+        let recipient = ink::primitives::Address::from([7u8; 20]);
+        client.call(&ink_e2e::alice(), &token_call.set_registry(Some(registry.addr))).submit().await.expect("set_registry failed");
+        let transfer = token_call.transfer(recipient, U256::from(25));
+        let result = client.call(&ink_e2e::alice(), &transfer).submit().await.expect("transfer failed");
+        assert_eq!(result.return_value(), Ok(()));
+        let balance = client.call(&ink_e2e::alice(), &token_call.balance_of(recipient)).dry_run().await?.return_value();
+        assert_eq!(balance, U256::from(25));
+        Ok(())
+    }
+
+    #[ink_e2e::test]
+    async fn transfer_to_blocked_code_hash_fails<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+        let mut constructor = Erc20Ref::new(U256::from(1000));
+        let token = client.instantiate("erc20", &ink_e2e::alice(), &mut constructor).submit().await.expect("erc20 instantiate failed");
+        let mut token_call = token.call_builder::<Erc20>();
+        let code = client.upload("mock_receiver", &ink_e2e::alice()).submit().await.expect("upload failed");
+        let mut receiver_constructor = MockReceiverRef::new(false);
+        let receiver = client.instantiate("mock_receiver", &ink_e2e::alice(), &mut receiver_constructor).submit().await.expect("receiver instantiate failed");
+
+        let block = token_call.set_code_hash_blocked(code.code_hash.0, true);
+        client.call(&ink_e2e::alice(), &block).submit().await.expect("block failed");
+        let transfer = token_call.transfer(receiver.addr, U256::from(10));
+        let result = client.call(&ink_e2e::alice(), &transfer).dry_run().await?.return_value();
+        assert_eq!(result, Err(Error::BlockedCodeHash));
+        Ok(())
+    }
+
+    #[ink_e2e::test]
+    async fn safe_transfer_skips_callback_for_registered_receiver<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+        let mut constructor = Erc20Ref::new(U256::from(1000));
+        let token = client.instantiate("erc20", &ink_e2e::alice(), &mut constructor).submit().await.expect("erc20 instantiate failed");
+        let mut token_call = token.call_builder::<Erc20>();
+        let mut receiver_constructor = MockReceiverRef::new(true);
+        let receiver = client.instantiate("mock_receiver", &ink_e2e::alice(), &mut receiver_constructor).submit().await.expect("receiver instantiate failed");
+        let mut receiver_call = receiver.call_builder::<MockReceiver>();
+
+        let transfer = token_call.safe_transfer(receiver.addr, U256::from(10));
+        let result = client.call(&ink_e2e::alice(), &transfer).dry_run().await?.return_value();
+        assert_eq!(result, Err(Error::ReceiverRejected));
+
+        client.call(&ink_e2e::alice(), &receiver_call.register_with(token.addr)).submit().await.expect("register failed");
+        let transfer = token_call.safe_transfer(receiver.addr, U256::from(10));
+        let result = client.call(&ink_e2e::alice(), &transfer).submit().await.expect("safe_transfer failed");
+        assert_eq!(result.return_value(), Ok(()));
+        let balance = client.call(&ink_e2e::alice(), &token_call.balance_of(receiver.addr)).dry_run().await?.return_value();
+        assert_eq!(balance, U256::from(10));
+        Ok(())
+    }
+
+    #[ink_e2e::test]
+    async fn safe_transfer_calls_back_unregistered_receiver<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+        let mut constructor = Erc20Ref::new(U256::from(1000));
+        let token = client.instantiate("erc20", &ink_e2e::alice(), &mut constructor).submit().await.expect("erc20 instantiate failed");
+        let mut token_call = token.call_builder::<Erc20>();
+        let mut receiver_constructor = MockReceiverRef::new(false);
+        let receiver = client.instantiate("mock_receiver", &ink_e2e::alice(), &mut receiver_constructor).submit().await.expect("receiver instantiate failed");
+        let mut receiver_call = receiver.call_builder::<MockReceiver>();
+
+        let alice = client.call(&ink_e2e::alice(), &token_call.owner()).dry_run().await?.return_value();
+        let transfer = token_call.safe_transfer(receiver.addr, U256::from(10));
+        client.call(&ink_e2e::alice(), &transfer).submit().await.expect("safe_transfer failed");
+        let last_received = client.call(&ink_e2e::alice(), &receiver_call.last_received()).dry_run().await?.return_value();
+        assert_eq!(last_received, Some((alice, U256::from(10))));
+        Ok(())
+    }
+}
@@ -1,10 +1,11 @@
 #[cfg(test)]
 mod tests {
     use ink::env::{test, DefaultEnvironment};
+    use ink::env::hash::Keccak256;
     use ink::primitives::Address;
     use ink::U256;
     use ink::scale::Decode;
-    use crate::erc20::{Erc20, Error};
+    use crate::erc20::{event_topics, AccountSnapshot, Erc20, Error, FeatureFlags, MintProposal, SpendPolicy, Stake, RESTRICTION_BLACKLISTED, RESTRICTION_FROZEN, SECONDS_PER_DAY, VERSION};
 
     fn setup() -> (Address, Address) {
         let alice: Address = Default::default();
@@ -59,8 +60,8 @@ mod tests {
         contract.transfer(bob, transfer_amount).unwrap();
 
         let events = test::recorded_events();
-        assert_eq!(events.len(), initial_events_len + 1);
-        let event = &events[events.len() - 1];
+        assert_eq!(events.len(), initial_events_len + 2);
+        let event = &events[events.len() - 2];
         let (from, to, value) = decode_transfer_event(&event.data);
         assert_eq!(from, Some(alice));
         assert_eq!(to, Some(bob));
@@ -126,8 +127,8 @@ mod tests {
         assert_eq!(result, Ok(()));
 
         let events = test::recorded_events();
-        assert_eq!(events.len(), initial_events_len + 1);
-        let event = &events[events.len() - 1];
+        assert_eq!(events.len(), initial_events_len + 2);
+        let event = &events[events.len() - 2];
         let (from, to, value) = decode_transfer_event(&event.data);
         assert_eq!(from, Some(alice));
         assert_eq!(to, Some(bob));
@@ -195,4 +196,2391 @@ mod tests {
         let contract = Erc20::new(U256::from(1000u32));
         assert_eq!(contract.balance_of(bob), U256::zero());
     }
-}
\ No newline at end of file
+
+    #[ink::test]
+    fn sync_burn_address_burns_zero_address_balance() {
+        let (_alice, bob) = setup();
+        let zero = Address::from([0u8; 20]);
+        test::set_caller(bob);
+        let initial_supply = U256::from(1000u32);
+        let mut contract = Erc20::new(initial_supply);
+        contract.transfer(zero, U256::from(300u32)).unwrap();
+        assert_eq!(contract.balance_of(zero), U256::from(300u32));
+
+        let burned = contract.sync_burn_address();
+        assert_eq!(burned, U256::from(300u32));
+        assert_eq!(contract.total_supply(), U256::from(700u32));
+        assert_eq!(contract.balance_of(zero), U256::zero());
+
+        let events = test::recorded_events();
+        let (from, to, value) = decode_transfer_event(&events[events.len() - 1].data);
+        assert_eq!(from, Some(zero));
+        assert_eq!(to, None);
+        assert_eq!(value, burned);
+
+        assert_eq!(contract.sync_burn_address(), U256::zero());
+    }
+
+    fn charlie() -> Address { [2u8; 20].into() }
+
+    #[ink::test]
+    fn fee_exempt_sender_pays_no_fee() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(1000u32)).unwrap();
+        contract.set_fee(100).unwrap();
+        contract.set_fee_exempt(bob, true).unwrap();
+        assert!(contract.is_fee_exempt(bob));
+
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(500u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(500u32));
+        assert_eq!(contract.balance_of(alice), U256::from(9000u32));
+    }
+
+    #[ink::test]
+    fn non_exempt_transfer_pays_fee() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(1000u32)).unwrap();
+        contract.set_fee(100).unwrap();
+        assert!(!contract.is_fee_exempt(bob));
+
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(500u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(500u32));
+        assert_eq!(contract.balance_of(charlie()), U256::from(495u32));
+        assert_eq!(contract.balance_of(alice), U256::from(9005u32));
+        assert_eq!(contract.total_supply(), U256::from(10_000u32));
+    }
+
+    #[ink::test]
+    fn set_fee_exempt_requires_owner() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        test::set_caller(bob);
+        assert_eq!(contract.set_fee_exempt(bob, true), Err(Error::NotOwner));
+        assert!(!contract.is_fee_exempt(bob));
+    }
+
+    #[ink::test]
+    fn total_supply_formatted_inserts_decimal_point() {
+        let contract = Erc20::with_metadata(U256::from(1500u32), "Token".into(), "TKN".into(), 2);
+        assert_eq!(contract.decimals(), 2);
+        assert_eq!(contract.total_supply_formatted(), "15.00");
+
+        let small = Erc20::with_metadata(U256::from(5u32), "Token".into(), "TKN".into(), 2);
+        assert_eq!(small.total_supply_formatted(), "0.05");
+    }
+
+    #[ink::test]
+    fn total_supply_formatted_handles_zero_supply() {
+        let contract = Erc20::new(U256::zero());
+        assert_eq!(contract.decimals(), 18);
+        assert_eq!(contract.total_supply_formatted(), "0");
+    }
+
+    #[ink::test]
+    fn transfer_during_fee_holiday_pays_no_fee() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.set_fee(100).unwrap();
+        contract.set_fee_holiday(1_000, 2_000).unwrap();
+
+        test::set_block_timestamp::<DefaultEnvironment>(1_500);
+        assert!(contract.in_fee_holiday());
+        contract.transfer(bob, U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(1000u32));
+    }
+
+    #[ink::test]
+    fn transfer_outside_fee_holiday_pays_fee() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(1000u32)).unwrap();
+        contract.set_fee(100).unwrap();
+        contract.set_fee_holiday(1_000, 2_000).unwrap();
+
+        test::set_block_timestamp::<DefaultEnvironment>(2_000);
+        assert!(!contract.in_fee_holiday());
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(990u32));
+    }
+
+    #[ink::test]
+    fn set_fee_holiday_rejects_inverted_window() {
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.set_fee_holiday(2_000, 1_000), Err(Error::InvalidWindow));
+    }
+
+    #[ink::test]
+    fn indexed_transfer_event_carries_magnitude_bucket() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(100_000u32));
+        contract.transfer(bob, U256::from(10u32)).unwrap();
+        let events_before = test::recorded_events().len();
+
+        contract.set_indexed_transfers(true).unwrap();
+        contract.transfer(bob, U256::from(12_345u32)).unwrap();
+
+        let events = test::recorded_events();
+        assert_eq!(events.len(), events_before + 3);
+        let (_, _, value) = decode_transfer_event(&events[events.len() - 3].data);
+        assert_eq!(value, U256::from(12_345u32));
+
+        let mut data = &events[events.len() - 2].data[..];
+        let _from: Option<Address> = Decode::decode(&mut data).unwrap();
+        let to: Option<Address> = Decode::decode(&mut data).unwrap();
+        let magnitude: u8 = Decode::decode(&mut data).unwrap();
+        let value: U256 = Decode::decode(&mut data).unwrap();
+        assert_eq!(to, Some(bob));
+        assert_eq!(magnitude, 4);
+        assert_eq!(value, U256::from(12_345u32));
+    }
+
+    #[ink::test]
+    fn fill_order_tracks_partial_fills() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(500u32)).unwrap();
+
+        test::set_caller(bob);
+        contract.fill_order(alice, charlie(), 1, U256::from(100u32), U256::from(60u32)).unwrap();
+        assert_eq!(contract.open_fill(alice, bob, 1), Some(U256::from(40u32)));
+        contract.fill_order(alice, charlie(), 1, U256::from(100u32), U256::from(40u32)).unwrap();
+        assert_eq!(contract.open_fill(alice, bob, 1), Some(U256::zero()));
+        assert_eq!(contract.balance_of(charlie()), U256::from(100u32));
+
+        let result = contract.fill_order(alice, charlie(), 1, U256::from(100u32), U256::from(1u32));
+        assert_eq!(result, Err(Error::Overfill));
+        assert_eq!(contract.balance_of(charlie()), U256::from(100u32));
+        assert_eq!(contract.allowance(alice, bob), U256::from(400u32));
+    }
+
+    #[ink::test]
+    fn fill_order_tracks_orders_between_the_same_pair_separately() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(500u32)).unwrap();
+
+        test::set_caller(bob);
+        contract.fill_order(alice, charlie(), 1, U256::from(100u32), U256::from(100u32)).unwrap();
+        contract.fill_order(alice, charlie(), 2, U256::from(50u32), U256::from(20u32)).unwrap();
+        assert_eq!(contract.open_fill(alice, bob, 1), Some(U256::zero()));
+        assert_eq!(contract.open_fill(alice, bob, 2), Some(U256::from(30u32)));
+        assert_eq!(contract.open_fill(alice, bob, 3), None);
+        assert_eq!(contract.balance_of(charlie()), U256::from(120u32));
+    }
+
+    #[ink::test]
+    fn transfers_per_block_are_capped_and_reset() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(100u32)).unwrap();
+        contract.set_max_transfers_per_block(2).unwrap();
+
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(1u32)).unwrap();
+        contract.transfer(charlie(), U256::from(1u32)).unwrap();
+        assert_eq!(contract.transfer(charlie(), U256::from(1u32)), Err(Error::TooManyTransfers));
+        assert_eq!(contract.balance_of(charlie()), U256::from(2u32));
+
+        test::advance_block::<DefaultEnvironment>();
+        contract.transfer(charlie(), U256::from(1u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(3u32));
+    }
+
+    #[ink::test]
+    fn owner_is_exempt_from_transfers_per_block_cap() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_max_transfers_per_block(1).unwrap();
+        contract.transfer(bob, U256::from(1u32)).unwrap();
+        contract.transfer(bob, U256::from(1u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(2u32));
+    }
+
+    #[ink::test]
+    fn features_reflect_current_state() {
+        let mut contract = Erc20::new(U256::from(1000u32));
+        let none = FeatureFlags {
+            has_fee: false,
+            fee_holiday: false,
+            indexed_transfers: false,
+            transfers_per_block_capped: false,
+            transfers_paused: false,
+            approvals_paused: false,
+            mints_paused: false,
+            config_locked: false,
+            whitelist_mode: false,
+            blacklist_active: false,
+            has_cap: false,
+        };
+        assert_eq!(contract.features(), none);
+
+        contract.set_fee(50).unwrap();
+        contract.set_indexed_transfers(true).unwrap();
+        assert_eq!(contract.features(), FeatureFlags { has_fee: true, indexed_transfers: true, ..none });
+
+        contract.set_whitelist_mode(true).unwrap();
+        assert!(contract.features().whitelist_mode);
+
+        contract.set_blacklisted(charlie(), true).unwrap();
+        assert!(contract.features().blacklist_active);
+        contract.set_blacklisted(charlie(), false).unwrap();
+        assert!(!contract.features().blacklist_active);
+
+        contract.set_cap(Some(U256::from(2000u32))).unwrap();
+        assert!(contract.features().has_cap);
+    }
+
+    #[ink::test]
+    fn compare_and_approve_with_matching_expected_value_works() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(100u32)).unwrap();
+        contract.compare_and_approve(bob, U256::from(100u32), U256::from(40u32)).unwrap();
+        assert_eq!(contract.allowance(alice, bob), U256::from(40u32));
+    }
+
+    #[ink::test]
+    fn compare_and_approve_with_stale_expected_value_fails() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(100u32)).unwrap();
+        let initial_events_len = test::recorded_events().len();
+
+        let result = contract.compare_and_approve(bob, U256::from(50u32), U256::from(40u32));
+        assert_eq!(result, Err(Error::AllowanceMismatch));
+        assert_eq!(contract.allowance(alice, bob), U256::from(100u32));
+        assert_eq!(test::recorded_events().len(), initial_events_len);
+    }
+
+    #[ink::test]
+    fn claim_before_deadline_mints_allocation() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_airdrop_allocation(bob, U256::from(50u32)).unwrap();
+        contract.set_claim_deadline(1_000).unwrap();
+
+        test::set_block_timestamp::<DefaultEnvironment>(1_000);
+        test::set_caller(bob);
+        contract.claim().unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(50u32));
+        assert_eq!(contract.total_supply(), U256::from(1050u32));
+        assert_eq!(contract.airdrop_claimed(), U256::from(50u32));
+        assert_eq!(contract.claim(), Err(Error::NothingToClaim));
+    }
+
+    #[ink::test]
+    fn claim_after_deadline_fails_and_remainder_is_swept() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_airdrop_allocation(bob, U256::from(50u32)).unwrap();
+        contract.set_airdrop_allocation(charlie(), U256::from(30u32)).unwrap();
+        assert_eq!(contract.airdrop_budget(), U256::from(80u32));
+        contract.set_claim_deadline(1_000).unwrap();
+
+        test::set_caller(bob);
+        contract.claim().unwrap();
+        test::set_caller(alice);
+        assert_eq!(contract.sweep_unclaimed(alice), Err(Error::ClaimWindowOpen));
+
+        test::set_block_timestamp::<DefaultEnvironment>(1_001);
+        test::set_caller(charlie());
+        assert_eq!(contract.claim(), Err(Error::ClaimWindowClosed));
+
+        let treasury: Address = [9u8; 20].into();
+        test::set_caller(alice);
+        contract.sweep_unclaimed(treasury).unwrap();
+        assert_eq!(contract.balance_of(treasury), U256::from(30u32));
+        assert_eq!(contract.total_supply(), U256::from(1080u32));
+        assert_eq!(contract.sweep_unclaimed(treasury), Err(Error::NothingToClaim));
+
+        contract.set_claim_deadline(5_000).unwrap();
+        test::set_caller(charlie());
+        assert_eq!(contract.claim(), Err(Error::ClaimWindowClosed));
+        assert_eq!(contract.total_supply(), U256::from(1080u32));
+    }
+
+    #[ink::test]
+    fn domain_separator_is_deterministic_and_name_bound() {
+        let first = Erc20::with_metadata(U256::from(1000u32), "Polka".into(), "PLK".into(), 18);
+        let separator = first.domain_separator();
+        assert_ne!(separator, [0u8; 32]);
+        assert_eq!(first.domain_separator(), separator);
+
+        let second = Erc20::with_metadata(U256::from(1000u32), "Other".into(), "PLK".into(), 18);
+        assert_ne!(second.domain_separator(), separator);
+    }
+
+    #[ink::test]
+    fn redenominate_split_multiplies_balances() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(200u32)).unwrap();
+
+        contract.redenominate(10, true).unwrap();
+        assert_eq!(contract.total_supply(), U256::from(10_000u32));
+        assert_eq!(contract.balance_of(alice), U256::from(8000u32));
+        assert_eq!(contract.balance_of(bob), U256::from(2000u32));
+
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(500u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(1500u32));
+        assert_eq!(contract.balance_of(charlie()), U256::from(500u32));
+    }
+
+    #[ink::test]
+    fn redenominate_merge_divides_balances() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(200u32)).unwrap();
+
+        contract.redenominate(10, false).unwrap();
+        assert_eq!(contract.total_supply(), U256::from(100u32));
+        assert_eq!(contract.balance_of(alice), U256::from(80u32));
+        assert_eq!(contract.balance_of(bob), U256::from(20u32));
+        assert_eq!(contract.scale_factor(), (U256::one(), U256::from(10u32)));
+        assert_eq!(contract.redenominate(0, true), Err(Error::InvalidFactor));
+    }
+
+    #[ink::test]
+    fn redenominate_reduces_the_scale_fraction() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(200u32)).unwrap();
+        for _ in 0..100 {
+            contract.redenominate(1_000_000, true).unwrap();
+            contract.redenominate(1_000_000, false).unwrap();
+        }
+        assert_eq!(contract.scale_factor(), (U256::one(), U256::one()));
+        assert_eq!(contract.balance_of(alice), U256::from(800u32));
+        assert_eq!(contract.balance_of(bob), U256::from(200u32));
+    }
+
+    fn rate(native_per_token: u32) -> U256 { U256::from(native_per_token) * U256::from(1_000_000_000_000_000_000u128) }
+
+    #[ink::test]
+    fn redeem_meeting_min_out_succeeds() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(100u32)).unwrap();
+        contract.set_redemption_rate(rate(2)).unwrap();
+        test::set_contract_balance(test::callee(), U256::from(1_000u32));
+        test::set_contract_balance(bob, U256::zero());
+
+        test::set_caller(bob);
+        contract.redeem(U256::from(10u32), U256::from(20u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(90u32));
+        assert_eq!(contract.total_supply(), U256::from(990u32));
+        assert_eq!(test::get_contract_balance::<DefaultEnvironment>(bob).unwrap(), U256::from(20u32));
+        assert_eq!(test::get_contract_balance::<DefaultEnvironment>(test::callee()).unwrap(), U256::from(980u32));
+    }
+
+    #[ink::test]
+    fn redeem_below_min_out_after_rate_change_fails() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(100u32)).unwrap();
+        contract.set_redemption_rate(rate(2)).unwrap();
+        test::set_contract_balance(test::callee(), U256::from(1_000u32));
+
+        contract.set_redemption_rate(rate(1)).unwrap();
+        test::set_caller(bob);
+        let result = contract.redeem(U256::from(10u32), U256::from(20u32));
+        assert_eq!(result, Err(Error::SlippageExceeded));
+        assert_eq!(contract.balance_of(bob), U256::from(100u32));
+        assert_eq!(contract.total_supply(), U256::from(1000u32));
+    }
+
+    #[ink::test]
+    fn account_state_matches_individual_getters() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(300u32)).unwrap();
+        contract.set_fee_exempt(bob, true).unwrap();
+        contract.set_airdrop_allocation(bob, U256::from(25u32)).unwrap();
+
+        test::set_block_timestamp::<DefaultEnvironment>(4_200);
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(100u32)).unwrap();
+
+        test::set_caller(alice);
+        contract.freeze_account(bob).unwrap();
+
+        let snapshot = contract.account_state(bob);
+        assert_eq!(snapshot, AccountSnapshot {
+            balance: contract.balance_of(bob),
+            fee_exempt: contract.is_fee_exempt(bob),
+            airdrop_allocation: contract.airdrop_allocation(bob),
+            last_transfer_at: contract.last_transfer_at(bob),
+            frozen: contract.is_frozen(bob),
+            blacklisted: contract.is_blacklisted(bob),
+        });
+        assert_eq!(snapshot.balance, U256::from(200u32));
+        assert!(snapshot.fee_exempt);
+        assert_eq!(snapshot.airdrop_allocation, U256::from(25u32));
+        assert_eq!(snapshot.last_transfer_at, 4_200);
+        assert!(snapshot.frozen);
+        assert!(!snapshot.blacklisted);
+    }
+
+    #[ink::test]
+    fn pay_to_account_transfers_without_callback() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        let initial_events_len = test::recorded_events().len();
+
+        contract.pay(bob, U256::from(100u32), 42).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(100u32));
+        assert_eq!(test::recorded_events().len(), initial_events_len + 2);
+    }
+
+    #[ink::test]
+    fn revoke_all_approvals_zeroes_every_spender() {
+        let (alice, bob) = setup();
+        let dave: Address = [3u8; 20].into();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(10u32)).unwrap();
+        contract.approve(charlie(), U256::from(20u32)).unwrap();
+        contract.approve(dave, U256::from(30u32)).unwrap();
+        contract.approve(bob, U256::from(15u32)).unwrap();
+        assert_eq!(contract.approved_spenders(alice), vec![bob, charlie(), dave]);
+        let initial_events_len = test::recorded_events().len();
+
+        contract.revoke_all_approvals().unwrap();
+        assert_eq!(contract.allowance(alice, bob), U256::zero());
+        assert_eq!(contract.allowance(alice, charlie()), U256::zero());
+        assert_eq!(contract.allowance(alice, dave), U256::zero());
+        assert!(contract.approved_spenders(alice).is_empty());
+
+        let events = test::recorded_events();
+        assert_eq!(events.len(), initial_events_len + 3);
+        for event in &events[initial_events_len..] {
+            let (owner, _spender, value) = decode_approval_event(&event.data);
+            assert_eq!(owner, alice);
+            assert_eq!(value, U256::zero());
+        }
+    }
+
+    #[ink::test]
+    fn min_transfer_is_enforced_for_non_zero_values() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_min_transfer(U256::from(10u32)).unwrap();
+
+        contract.transfer(bob, U256::from(10u32)).unwrap();
+        assert_eq!(contract.transfer(bob, U256::from(9u32)), Err(Error::BelowMinTransfer));
+        contract.transfer(bob, U256::zero()).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(10u32));
+    }
+
+    fn keccak(input: &[u8]) -> [u8; 32] {
+        let mut output = [0u8; 32];
+        ink::env::hash_bytes::<Keccak256>(input, &mut output);
+        output
+    }
+
+    fn word(bytes: &[u8]) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[32 - bytes.len()..].copy_from_slice(bytes);
+        word
+    }
+
+    fn signer_key() -> (secp256k1::SecretKey, Address) {
+        let secp = secp256k1::Secp256k1::new();
+        let key = secp256k1::SecretKey::from_slice(&[0x42u8; 32]).unwrap();
+        let public_key = secp256k1::PublicKey::from_secret_key(&secp, &key).serialize_uncompressed();
+        let hash = keccak(&public_key[1..]);
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hash[12..]);
+        (key, address.into())
+    }
+
+    fn sign_typed(contract: &Erc20, key: &secp256k1::SecretKey, struct_words: &[[u8; 32]]) -> [u8; 65] {
+        let struct_hash = keccak(&struct_words.concat());
+        let digest = keccak(&[&[0x19u8, 0x01][..], &contract.domain_separator(), &struct_hash].concat());
+        let message = secp256k1::Message::from_digest_slice(&digest).unwrap();
+        let (recovery_id, compact) = secp256k1::Secp256k1::new().sign_ecdsa_recoverable(&message, key).serialize_compact();
+        let mut signature = [0u8; 65];
+        signature[..64].copy_from_slice(&compact);
+        signature[64] = recovery_id.to_i32() as u8;
+        signature
+    }
+
+    fn sign_transfer_authorization(
+        contract: &Erc20,
+        key: &secp256k1::SecretKey,
+        (from, to, value): (Address, Address, u64),
+        (valid_after, valid_before): (u64, u64),
+        nonce: [u8; 32],
+    ) -> [u8; 65] {
+        let type_hash = keccak(b"TransferWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)");
+        let words = [
+            type_hash,
+            word(from.as_bytes()),
+            word(to.as_bytes()),
+            word(&value.to_be_bytes()),
+            word(&valid_after.to_be_bytes()),
+            word(&valid_before.to_be_bytes()),
+            nonce,
+        ];
+        sign_typed(contract, key, &words)
+    }
+
+    #[ink::test]
+    fn transfer_with_authorization_works_once() {
+        let (_alice, bob) = setup();
+        let (key, signer) = signer_key();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(signer, U256::from(100u32)).unwrap();
+        test::set_block_timestamp::<DefaultEnvironment>(500);
+
+        let nonce = [7u8; 32];
+        let signature = sign_transfer_authorization(&contract, &key, (signer, bob, 60), (0, 1_000), nonce);
+        test::set_caller(charlie());
+        contract.transfer_with_authorization(signer, bob, U256::from(60u32), 0, 1_000, nonce, signature).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(60u32));
+        assert_eq!(contract.balance_of(signer), U256::from(40u32));
+        assert!(contract.authorization_state(signer, nonce));
+
+        let replay = contract.transfer_with_authorization(signer, bob, U256::from(60u32), 0, 1_000, nonce, signature);
+        assert_eq!(replay, Err(Error::AuthorizationUsed));
+        assert_eq!(contract.balance_of(bob), U256::from(60u32));
+    }
+
+    #[ink::test]
+    fn transfer_with_authorization_checks_window_and_signer() {
+        let (_alice, bob) = setup();
+        let (key, signer) = signer_key();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(signer, U256::from(100u32)).unwrap();
+        let nonce = [8u8; 32];
+        let signature = sign_transfer_authorization(&contract, &key, (signer, bob, 10), (100, 200), nonce);
+
+        test::set_block_timestamp::<DefaultEnvironment>(100);
+        let early = contract.transfer_with_authorization(signer, bob, U256::from(10u32), 100, 200, nonce, signature);
+        assert_eq!(early, Err(Error::AuthorizationNotYetValid));
+        test::set_block_timestamp::<DefaultEnvironment>(200);
+        let late = contract.transfer_with_authorization(signer, bob, U256::from(10u32), 100, 200, nonce, signature);
+        assert_eq!(late, Err(Error::AuthorizationExpired));
+
+        test::set_block_timestamp::<DefaultEnvironment>(150);
+        let tampered = contract.transfer_with_authorization(signer, bob, U256::from(11u32), 100, 200, nonce, signature);
+        assert_eq!(tampered, Err(Error::InvalidSignature));
+        assert_eq!(contract.balance_of(bob), U256::zero());
+    }
+
+    #[ink::test]
+    fn cancelled_authorization_cannot_be_used() {
+        let (_alice, bob) = setup();
+        let (key, signer) = signer_key();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(signer, U256::from(100u32)).unwrap();
+        test::set_block_timestamp::<DefaultEnvironment>(500);
+
+        let nonce = [9u8; 32];
+        let cancel_type = keccak(b"CancelAuthorization(address authorizer,bytes32 nonce)");
+        let cancel_signature = sign_typed(&contract, &key, &[cancel_type, word(signer.as_bytes()), nonce]);
+        contract.cancel_authorization(signer, nonce, cancel_signature).unwrap();
+        assert!(contract.authorization_state(signer, nonce));
+
+        let signature = sign_transfer_authorization(&contract, &key, (signer, bob, 10), (0, 1_000), nonce);
+        let result = contract.transfer_with_authorization(signer, bob, U256::from(10u32), 0, 1_000, nonce, signature);
+        assert_eq!(result, Err(Error::AuthorizationUsed));
+        assert_eq!(contract.balance_of(bob), U256::zero());
+    }
+
+    #[ink::test]
+    fn overview_matches_individual_getters() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(250u32)).unwrap();
+        test::set_caller(bob);
+        contract.approve(charlie(), U256::from(75u32)).unwrap();
+
+        let (total_supply, balance, allowance) = contract.overview(charlie());
+        assert_eq!(total_supply, contract.total_supply());
+        assert_eq!(balance, contract.balance_of(bob));
+        assert_eq!(allowance, contract.allowance(bob, charlie()));
+        assert_eq!((balance, allowance), (U256::from(250u32), U256::from(75u32)));
+    }
+
+    #[ink::test]
+    fn total_granted_to_saturates_on_unlimited_approvals() {
+        let (alice, bob) = setup();
+        let spender = charlie();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(spender, U256::MAX).unwrap();
+        test::set_caller(bob);
+        contract.approve(spender, U256::MAX).unwrap();
+        assert_eq!(contract.total_granted_to(spender), U256::MAX);
+        assert_eq!(contract.allowance(alice, spender), U256::MAX);
+        assert_eq!(contract.allowance(bob, spender), U256::MAX);
+    }
+
+    #[ink::test]
+    fn total_granted_to_aggregates_across_owners() {
+        let (_alice, bob) = setup();
+        let spender = charlie();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(spender, U256::from(100u32)).unwrap();
+        test::set_caller(bob);
+        contract.approve(spender, U256::from(50u32)).unwrap();
+        assert_eq!(contract.total_granted_to(spender), U256::from(150u32));
+
+        contract.increase_allowance(spender, U256::from(25u32)).unwrap();
+        assert_eq!(contract.total_granted_to(spender), U256::from(175u32));
+        contract.decrease_allowance(spender, U256::from(60u32)).unwrap();
+        assert_eq!(contract.allowance(bob, spender), U256::from(15u32));
+        assert_eq!(contract.total_granted_to(spender), U256::from(115u32));
+        assert_eq!(contract.decrease_allowance(spender, U256::from(16u32)), Err(Error::InsufficientAllowance));
+    }
+
+    #[ink::test]
+    fn total_granted_to_drops_when_allowance_is_spent() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(100u32)).unwrap();
+        test::set_caller(bob);
+        contract.transfer_from(alice, bob, U256::from(40u32)).unwrap();
+        assert_eq!(contract.total_granted_to(bob), U256::from(60u32));
+    }
+
+    #[ink::test]
+    fn transfers_paused_blocks_only_transfers() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_airdrop_allocation(bob, U256::from(5u32)).unwrap();
+        contract.set_transfers_paused(true).unwrap();
+
+        assert_eq!(contract.transfer(bob, U256::from(1u32)), Err(Error::TransfersPaused));
+        contract.approve(bob, U256::from(10u32)).unwrap();
+        test::set_caller(bob);
+        assert_eq!(contract.transfer_from(alice, bob, U256::from(1u32)), Err(Error::TransfersPaused));
+        contract.claim().unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(5u32));
+    }
+
+    #[ink::test]
+    fn approvals_paused_blocks_only_approvals() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(10u32)).unwrap();
+        contract.set_approvals_paused(true).unwrap();
+
+        assert_eq!(contract.approve(bob, U256::from(20u32)), Err(Error::ApprovalsPaused));
+        assert_eq!(contract.increase_allowance(bob, U256::from(1u32)), Err(Error::ApprovalsPaused));
+        contract.decrease_allowance(bob, U256::from(4u32)).unwrap();
+        contract.approve(bob, U256::from(3u32)).unwrap();
+        assert_eq!(contract.allowance(alice, bob), U256::from(3u32));
+        contract.transfer(bob, U256::from(1u32)).unwrap();
+        assert!(contract.features().approvals_paused);
+    }
+
+    #[ink::test]
+    fn mints_paused_blocks_only_mints() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_airdrop_allocation(bob, U256::from(5u32)).unwrap();
+        contract.set_mints_paused(true).unwrap();
+
+        contract.transfer(bob, U256::from(1u32)).unwrap();
+        contract.approve(bob, U256::from(1u32)).unwrap();
+        test::set_caller(bob);
+        assert_eq!(contract.claim(), Err(Error::MintsPaused));
+        assert_eq!(contract.total_supply(), U256::from(1000u32));
+    }
+
+    #[ink::test]
+    fn implied_price_reflects_reserve_per_token() {
+        let mut contract = Erc20::new(U256::from(1000u32));
+        test::set_contract_balance(test::callee(), U256::from(500u32));
+        assert_eq!(contract.implied_price(), U256::from(500_000_000_000_000_000u128));
+
+        contract.set_airdrop_allocation(charlie(), U256::from(1000u32)).unwrap();
+        test::set_caller(charlie());
+        contract.claim().unwrap();
+        assert_eq!(contract.implied_price(), U256::from(250_000_000_000_000_000u128));
+    }
+
+    #[ink::test]
+    fn implied_price_is_zero_without_supply() {
+        let contract = Erc20::new(U256::zero());
+        test::set_contract_balance(test::callee(), U256::from(500u32));
+        assert_eq!(contract.implied_price(), U256::zero());
+    }
+
+    #[ink::test]
+    fn tax_sink_retains_tax_and_forwards_remainder() {
+        let (_alice, bob) = setup();
+        let sink: Address = [5u8; 20].into();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(2000u32)).unwrap();
+        contract.set_fee(500).unwrap();
+        contract.set_tax_sink(Some(sink)).unwrap();
+
+        test::set_caller(bob);
+        let initial_events_len = test::recorded_events().len();
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(1000u32));
+        assert_eq!(contract.balance_of(sink), U256::from(50u32));
+        assert_eq!(contract.balance_of(charlie()), U256::from(950u32));
+
+        let events = test::recorded_events();
+        assert_eq!(events.len(), initial_events_len + 3);
+        assert_eq!(decode_transfer_event(&events[initial_events_len].data), (Some(bob), Some(sink), U256::from(1000u32)));
+        assert_eq!(decode_transfer_event(&events[initial_events_len + 1].data), (Some(sink), Some(charlie()), U256::from(950u32)));
+    }
+
+    #[ink::test]
+    fn tax_sink_events_exclude_reflection() {
+        let (_alice, bob) = setup();
+        let sink: Address = [5u8; 20].into();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(2000u32)).unwrap();
+        contract.set_fee(500).unwrap();
+        contract.set_reflection_bps(1000).unwrap();
+        contract.set_tax_sink(Some(sink)).unwrap();
+
+        test::set_caller(bob);
+        let initial_events_len = test::recorded_events().len();
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        let events = test::recorded_events();
+        assert_eq!(decode_transfer_event(&events[initial_events_len].data), (Some(bob), Some(sink), U256::from(905u32)));
+        assert_eq!(decode_transfer_event(&events[initial_events_len + 1].data), (Some(sink), Some(charlie()), U256::from(855u32)));
+    }
+
+    #[ink::test]
+    fn tax_sink_is_bypassed_by_owner_and_exempt_accounts() {
+        let (_alice, bob) = setup();
+        let sink: Address = [5u8; 20].into();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.set_fee(500).unwrap();
+        contract.set_tax_sink(Some(sink)).unwrap();
+        contract.transfer(bob, U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(1000u32));
+
+        contract.set_fee_exempt(bob, true).unwrap();
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(1000u32));
+        assert_eq!(contract.balance_of(sink), U256::zero());
+    }
+
+    #[ink::test]
+    fn event_topics_match_emitted_signature_topics() {
+        let topics = event_topics();
+        let (name, transfer_topic) = &topics[0];
+        assert_eq!(name, "Transfer");
+        assert_ne!(*transfer_topic, [0u8; 32]);
+        assert_eq!(event_topics(), topics);
+
+        let _contract = Erc20::new(U256::from(1000u32));
+        let events = test::recorded_events();
+        assert_eq!(events[0].topics[0].as_slice(), &transfer_topic[..]);
+
+        let mut seen: Vec<[u8; 32]> = topics.iter().map(|(_, topic)| *topic).collect();
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), topics.len());
+    }
+
+    #[ink::test]
+    fn locked_configuration_rejects_setters_but_allows_pause() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_fee(100).unwrap();
+        contract.lock_configuration().unwrap();
+        assert!(contract.config_locked());
+
+        assert_eq!(contract.set_fee(200), Err(Error::ConfigLocked));
+        assert_eq!(contract.set_min_transfer(U256::from(5u32)), Err(Error::ConfigLocked));
+        assert_eq!(contract.lock_configuration(), Err(Error::ConfigLocked));
+        assert_eq!(contract.fee_bps(), 100);
+
+        contract.set_transfers_paused(true).unwrap();
+        assert_eq!(contract.transfer(bob, U256::from(1u32)), Err(Error::TransfersPaused));
+        contract.transfer_ownership(bob).unwrap();
+        assert_eq!(contract.owner(), bob);
+    }
+
+    #[ink::test]
+    fn burned_total_accumulates_across_burn_paths() {
+        let (alice, bob) = setup();
+        let zero = Address::from([0u8; 20]);
+        test::set_caller(bob);
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.burn(U256::from(10u32)).unwrap();
+        assert_eq!(contract.burned_total(), U256::from(10u32));
+
+        contract.approve(alice, U256::from(30u32)).unwrap();
+        contract.transfer(zero, U256::from(5u32)).unwrap();
+        test::set_caller(alice);
+        contract.burn_from(bob, U256::from(20u32)).unwrap();
+        assert_eq!(contract.allowance(bob, alice), U256::from(10u32));
+        assert_eq!(contract.burn_from(bob, U256::from(11u32)), Err(Error::InsufficientAllowance));
+        contract.sync_burn_address();
+
+        assert_eq!(contract.burned_total(), U256::from(35u32));
+        assert_eq!(contract.total_supply(), U256::from(965u32));
+        assert_eq!(contract.burn(U256::from(1u32)), Err(Error::InsufficientBalance));
+    }
+
+    #[ink::test]
+    fn staking_accrues_and_mints_rewards() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(1000u32)).unwrap();
+        // 1% of the staked amount per second.
+        contract.set_reward_rate_per_sec(U256::from(10_000_000_000_000_000u128)).unwrap();
+
+        test::set_caller(bob);
+        test::set_block_timestamp::<DefaultEnvironment>(100);
+        contract.stake(U256::from(500u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(500u32));
+        assert_eq!(contract.total_staked(), U256::from(500u32));
+
+        test::set_block_timestamp::<DefaultEnvironment>(110);
+        assert_eq!(contract.stake_of(bob), Stake { amount: U256::from(500u32), since: 110, accrued: U256::from(50u32) });
+        contract.claim_rewards().unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(550u32));
+        assert_eq!(contract.total_supply(), U256::from(10_050u32));
+        assert_eq!(contract.claim_rewards(), Err(Error::NothingToClaim));
+    }
+
+    #[ink::test]
+    fn unstake_returns_tokens_and_rejects_excess() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(1000u32)).unwrap();
+
+        test::set_caller(bob);
+        contract.stake(U256::from(400u32)).unwrap();
+        assert_eq!(contract.unstake(U256::from(401u32)), Err(Error::InsufficientStake));
+        contract.unstake(U256::from(150u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(750u32));
+        assert_eq!(contract.stake_of(bob).amount, U256::from(250u32));
+        assert_eq!(contract.total_staked(), U256::from(250u32));
+    }
+
+    #[ink::test]
+    fn redenominate_waits_for_stakes_to_unwind() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(1000u32)).unwrap();
+        test::set_caller(bob);
+        contract.stake(U256::from(400u32)).unwrap();
+
+        test::set_caller(alice);
+        assert_eq!(contract.redenominate(10, false), Err(Error::EscrowOutstanding));
+        test::set_caller(bob);
+        contract.unstake(U256::from(400u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(1000u32));
+
+        test::set_caller(alice);
+        contract.redenominate(10, false).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(100u32));
+    }
+
+    #[ink::test]
+    fn blocked_code_hash_does_not_affect_accounts() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_code_hash_blocked([0xabu8; 32], true).unwrap();
+        assert!(contract.is_code_hash_blocked([0xabu8; 32]));
+
+        contract.transfer(bob, U256::from(10u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(10u32));
+        test::set_caller(bob);
+        assert_eq!(contract.set_code_hash_blocked([0xabu8; 32], false), Err(Error::NotOwner));
+    }
+
+    #[ink::test]
+    fn max_pullable_is_bound_by_allowance_or_balance() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(300u32)).unwrap();
+        assert_eq!(contract.max_pullable(alice, bob), U256::from(300u32));
+
+        contract.approve(bob, U256::from(5000u32)).unwrap();
+        assert_eq!(contract.max_pullable(alice, bob), U256::from(1000u32));
+
+        contract.approve(bob, U256::from(1000u32)).unwrap();
+        assert_eq!(contract.max_pullable(alice, bob), U256::from(1000u32));
+    }
+
+    #[ink::test]
+    fn register_as_receiver_records_caller_and_safe_transfer_to_account_works() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert!(!contract.is_registered_receiver(bob));
+        test::set_caller(bob);
+        contract.register_as_receiver();
+        assert!(contract.is_registered_receiver(bob));
+
+        test::set_caller(alice);
+        contract.safe_transfer(charlie(), U256::from(10u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(10u32));
+    }
+
+    fn alias(name: &str) -> [u8; 32] {
+        let mut alias = [0u8; 32];
+        alias[..name.len()].copy_from_slice(name.as_bytes());
+        alias
+    }
+
+    #[ink::test]
+    fn transfer_to_alias_resolves_registered_name() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_alias(alias("bob"), bob).unwrap();
+        assert_eq!(contract.resolve_alias(alias("bob")), Some(bob));
+
+        contract.transfer_to_alias(alias("bob"), U256::from(25u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(25u32));
+    }
+
+    #[ink::test]
+    fn transfer_to_unknown_alias_fails() {
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.resolve_alias(alias("nobody")), None);
+        assert_eq!(contract.transfer_to_alias(alias("nobody"), U256::from(1u32)), Err(Error::UnknownAlias));
+    }
+
+    #[ink::test]
+    fn current_apr_annualises_reward_rate() {
+        let mut contract = Erc20::new(U256::from(1000u32));
+        // Just under 10% a year: floor(3_170_979_198 * 31_536_000 * 10_000 / 1e18) = 999 bps.
+        contract.set_reward_rate_per_sec(U256::from(3_170_979_198u64)).unwrap();
+        assert_eq!(contract.current_apr(), U256::zero());
+
+        contract.stake(U256::from(100u32)).unwrap();
+        assert_eq!(contract.current_apr(), U256::from(999u32));
+    }
+
+    #[ink::test]
+    fn recovery_executes_only_after_eta() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(100u32)).unwrap();
+        contract.set_recovery_delay(3_600).unwrap();
+        assert_eq!(contract.execute_recovery(), Err(Error::NoPendingRecovery));
+
+        test::set_block_timestamp::<DefaultEnvironment>(1_000);
+        contract.propose_recovery(bob, alice, U256::from(60u32)).unwrap();
+        assert_eq!(contract.pending_recovery().map(|recovery| recovery.eta), Some(4_600));
+
+        test::set_block_timestamp::<DefaultEnvironment>(4_599);
+        assert_eq!(contract.execute_recovery(), Err(Error::RecoveryNotReady));
+        assert_eq!(contract.balance_of(bob), U256::from(100u32));
+
+        test::set_block_timestamp::<DefaultEnvironment>(4_600);
+        contract.execute_recovery().unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(40u32));
+        assert_eq!(contract.pending_recovery(), None);
+    }
+
+    #[ink::test]
+    fn reflection_grows_holder_balances_proportionally() {
+        let (alice, bob) = setup();
+        let dave: Address = [3u8; 20].into();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(3000u32)).unwrap();
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        contract.transfer(dave, U256::from(1000u32)).unwrap();
+        contract.set_reflection_bps(1000).unwrap();
+
+        // 100 is reflected onto the 4000 eligible tokens held by bob and charlie (alice is the owner).
+        test::set_caller(dave);
+        contract.transfer(alice, U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(alice), U256::from(5900u32));
+        assert_eq!(contract.balance_of(bob), U256::from(3075u32));
+        assert_eq!(contract.balance_of(charlie()), U256::from(1025u32));
+        assert_eq!(contract.balance_of(dave), U256::zero());
+        assert!(contract.is_excluded_from_reflection(alice));
+
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(75u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(3005u32));
+        assert!(contract.balance_of(bob) + contract.balance_of(charlie()) + contract.balance_of(alice) <= U256::from(10_000u32));
+    }
+
+    #[ink::test]
+    fn aggregate_balance_includes_confirmed_subaccounts() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(300u32)).unwrap();
+        contract.transfer(charlie(), U256::from(200u32)).unwrap();
+
+        test::set_caller(bob);
+        contract.link_subaccount(charlie()).unwrap();
+        assert_eq!(contract.aggregate_balance(bob), U256::from(300u32));
+
+        test::set_caller(charlie());
+        assert_eq!(contract.confirm_link(alice), Err(Error::NoPendingLink));
+        contract.confirm_link(bob).unwrap();
+        assert_eq!(contract.main_account_of(charlie()), Some(bob));
+        assert_eq!(contract.subaccounts_of(bob), vec![charlie()]);
+        assert_eq!(contract.aggregate_balance(bob), U256::from(500u32));
+
+        test::set_caller(alice);
+        assert_eq!(contract.link_subaccount(charlie()), Err(Error::AlreadyLinked));
+    }
+
+    #[ink::test]
+    fn reserves_reports_native_balance_and_supply() {
+        let contract = Erc20::new(U256::from(1000u32));
+        test::set_contract_balance(test::callee(), U256::from(250u32));
+        assert_eq!(contract.reserves(), (U256::from(250u32), U256::from(1000u32)));
+    }
+
+    #[ink::test]
+    fn invalidate_all_allowances_voids_earlier_approvals() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(100u32)).unwrap();
+        contract.invalidate_all_allowances().unwrap();
+        assert_eq!(contract.approval_epoch(), 1);
+        assert_eq!(contract.allowance(alice, bob), U256::zero());
+
+        test::set_caller(bob);
+        assert_eq!(contract.transfer_from(alice, bob, U256::from(10u32)), Err(Error::InsufficientAllowance));
+        assert_eq!(contract.invalidate_all_allowances(), Err(Error::NotOwner));
+
+        test::set_caller(alice);
+        contract.approve(bob, U256::from(50u32)).unwrap();
+        test::set_caller(bob);
+        contract.transfer_from(alice, bob, U256::from(10u32)).unwrap();
+        assert_eq!(contract.allowance(alice, bob), U256::from(40u32));
+        assert_eq!(contract.total_granted_to(bob), U256::from(40u32));
+    }
+
+    #[ink::test]
+    fn transfer_split_distributes_by_weight() {
+        let (alice, bob) = setup();
+        let dave: Address = [3u8; 20].into();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        let initial_events_len = test::recorded_events().len();
+        contract.transfer_split(vec![bob, charlie(), dave], vec![1, 2, 1], U256::from(100u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(25u32));
+        assert_eq!(contract.balance_of(charlie()), U256::from(50u32));
+        assert_eq!(contract.balance_of(dave), U256::from(25u32));
+        assert_eq!(test::recorded_events().len(), initial_events_len + 6);
+
+        // 101 / 4 leaves a remainder that lands on the last recipient.
+        contract.transfer_split(vec![bob, charlie(), dave], vec![1, 2, 1], U256::from(101u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(50u32));
+        assert_eq!(contract.balance_of(charlie()), U256::from(100u32));
+        assert_eq!(contract.balance_of(dave), U256::from(51u32));
+        assert_eq!(contract.balance_of(alice), U256::from(799u32));
+    }
+
+    #[ink::test]
+    fn transfer_split_rejects_bad_weights() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.transfer_split(vec![bob, charlie()], vec![1], U256::from(10u32)), Err(Error::LengthMismatch));
+        assert_eq!(contract.transfer_split(vec![bob], vec![0], U256::from(10u32)), Err(Error::ZeroWeight));
+        assert_eq!(contract.transfer_split(vec![], vec![], U256::from(10u32)), Err(Error::ZeroWeight));
+    }
+
+    #[ink::test]
+    fn simulate_transfer_matches_actual_outcome() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(500u32)).unwrap();
+        contract.set_fee(100).unwrap();
+
+        test::set_caller(bob);
+        let simulated = contract.simulate_transfer(bob, charlie(), U256::from(200u32)).unwrap();
+        assert_eq!(simulated, (U256::from(300u32), U256::from(198u32)));
+        contract.transfer(charlie(), U256::from(200u32)).unwrap();
+        assert_eq!(simulated, (contract.balance_of(bob), contract.balance_of(charlie())));
+
+        assert_eq!(contract.simulate_transfer(bob, alice, U256::from(301u32)), Err(Error::InsufficientBalance));
+        test::set_caller(alice);
+        contract.set_transfers_paused(true).unwrap();
+        assert_eq!(contract.simulate_transfer(bob, alice, U256::from(1u32)), Err(Error::TransfersPaused));
+    }
+
+    #[ink::test]
+    fn allowance_cap_clamps_to_balance() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(5000u32)).unwrap();
+        assert_eq!(contract.allowance(alice, bob), U256::from(5000u32));
+
+        contract.set_cap_allowance_to_balance(true).unwrap();
+        contract.approve(bob, U256::from(5000u32)).unwrap();
+        assert_eq!(contract.allowance(alice, bob), U256::from(1000u32));
+        let events = test::recorded_events();
+        let (_, _, value) = decode_approval_event(&events.last().unwrap().data);
+        assert_eq!(value, U256::from(1000u32));
+
+        contract.approve(bob, U256::from(900u32)).unwrap();
+        contract.increase_allowance(bob, U256::from(500u32)).unwrap();
+        assert_eq!(contract.allowance(alice, bob), U256::from(1000u32));
+    }
+
+    #[ink::test]
+    fn holders_page_returns_bounded_windows() {
+        let (alice, bob) = setup();
+        let dave: Address = [3u8; 20].into();
+        let eve: Address = [4u8; 20].into();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(10u32)).unwrap();
+        contract.transfer(charlie(), U256::from(20u32)).unwrap();
+        contract.transfer(dave, U256::from(30u32)).unwrap();
+        contract.transfer(eve, U256::from(40u32)).unwrap();
+
+        assert_eq!(contract.holders_page(0, 2), vec![(alice, U256::from(900u32)), (bob, U256::from(10u32))]);
+        assert_eq!(contract.holders_page(2, 2), vec![(charlie(), U256::from(20u32)), (dave, U256::from(30u32))]);
+        assert_eq!(contract.holders_page(4, 10), vec![(eve, U256::from(40u32))]);
+        assert!(contract.holders_page(5, 10).is_empty());
+
+        test::set_caller(bob);
+        contract.transfer(alice, U256::from(10u32)).unwrap();
+        assert_eq!(contract.holders_page(0, 3), vec![(alice, U256::from(910u32)), (charlie(), U256::from(20u32))]);
+    }
+
+    #[ink::test]
+    fn fee_decays_linearly_over_window() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(100_000u32));
+        contract.transfer(bob, U256::from(30_000u32)).unwrap();
+        contract.set_fee_decay(2000, 0, 1000, 2000).unwrap();
+        test::set_caller(bob);
+
+        test::set_block_timestamp::<DefaultEnvironment>(500);
+        assert_eq!(contract.current_fee_bps(), 2000);
+        contract.transfer(charlie(), U256::from(10_000u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(8_000u32));
+
+        test::set_block_timestamp::<DefaultEnvironment>(1500);
+        assert_eq!(contract.current_fee_bps(), 1000);
+        contract.transfer(charlie(), U256::from(10_000u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(17_000u32));
+
+        test::set_block_timestamp::<DefaultEnvironment>(2000);
+        assert_eq!(contract.current_fee_bps(), 0);
+        contract.transfer(charlie(), U256::from(10_000u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(27_000u32));
+    }
+
+    #[ink::test]
+    fn fee_decay_rejects_invalid_schedule() {
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.set_fee_decay(10_001, 0, 0, 10), Err(Error::InvalidFee));
+        assert_eq!(contract.set_fee_decay(100, 0, 10, 5), Err(Error::InvalidWindow));
+    }
+
+    #[ink::test]
+    fn would_trip_breaker_compares_against_threshold() {
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        assert!(!contract.would_trip_breaker(U256::from(10_000u32)));
+        contract.set_breaker_threshold_bps(500).unwrap();
+        assert!(!contract.would_trip_breaker(U256::from(10u32)));
+        assert!(!contract.would_trip_breaker(U256::from(500u32)));
+        assert!(contract.would_trip_breaker(U256::from(501u32)));
+    }
+
+    #[ink::test]
+    fn large_transfer_trips_breaker() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(2000u32)).unwrap();
+        contract.set_breaker_threshold_bps(500).unwrap();
+
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(500u32)).unwrap();
+        assert!(!contract.transfers_paused());
+        contract.transfer(charlie(), U256::from(501u32)).unwrap();
+        assert!(contract.transfers_paused());
+        assert_eq!(contract.transfer(charlie(), U256::from(1u32)), Err(Error::TransfersPaused));
+    }
+
+    #[ink::test]
+    fn mint_requires_minter_role() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        test::set_caller(bob);
+        assert_eq!(contract.mint(bob, U256::from(10u32)), Err(Error::NotMinter));
+
+        test::set_caller(alice);
+        contract.set_minter(bob, true).unwrap();
+        test::set_caller(bob);
+        contract.mint(charlie(), U256::from(10u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(10u32));
+        assert_eq!(contract.total_supply(), U256::from(1010u32));
+    }
+
+    #[ink::test]
+    fn mint_with_reason_emits_reason() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        let initial_events_len = test::recorded_events().len();
+        contract.mint_with_reason(bob, U256::from(50u32), String::from("Q3 grant")).unwrap();
+
+        let events = test::recorded_events();
+        assert_eq!(events.len(), initial_events_len + 2);
+        let mut data = &events[events.len() - 1].data[..];
+        let to: Address = Decode::decode(&mut data).unwrap();
+        let value: U256 = Decode::decode(&mut data).unwrap();
+        let reason: String = Decode::decode(&mut data).unwrap();
+        assert_eq!((to, value, reason.as_str()), (bob, U256::from(50u32), "Q3 grant"));
+    }
+
+    #[ink::test]
+    fn transfer_from_waits_for_approval_cooldown() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_approval_cooldown(60).unwrap();
+        test::set_block_timestamp::<DefaultEnvironment>(1000);
+        contract.approve(bob, U256::from(100u32)).unwrap();
+
+        test::set_caller(bob);
+        assert_eq!(contract.transfer_from(alice, bob, U256::from(10u32)), Err(Error::ApprovalTooRecent));
+        test::set_block_timestamp::<DefaultEnvironment>(1059);
+        assert_eq!(contract.transfer_from(alice, bob, U256::from(10u32)), Err(Error::ApprovalTooRecent));
+        test::set_block_timestamp::<DefaultEnvironment>(1060);
+        contract.transfer_from(alice, bob, U256::from(10u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(10u32));
+    }
+
+    #[ink::test]
+    fn revoke_approvals_zeroes_only_listed_spenders() {
+        let (alice, bob) = setup();
+        let dave: Address = [3u8; 20].into();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(10u32)).unwrap();
+        contract.approve(charlie(), U256::from(20u32)).unwrap();
+        contract.approve(dave, U256::from(30u32)).unwrap();
+
+        let initial_events_len = test::recorded_events().len();
+        contract.revoke_approvals(vec![bob, dave]).unwrap();
+        assert_eq!(test::recorded_events().len(), initial_events_len + 2);
+        assert_eq!(contract.allowance(alice, bob), U256::zero());
+        assert_eq!(contract.allowance(alice, dave), U256::zero());
+        assert_eq!(contract.allowance(alice, charlie()), U256::from(20u32));
+        assert_eq!(contract.approved_spenders(alice), vec![charlie()]);
+    }
+
+    #[ink::test]
+    fn transfer_all_empties_the_sender() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(777u32)).unwrap();
+        contract.set_fee(100).unwrap();
+
+        test::set_caller(bob);
+        contract.transfer_all(charlie()).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::zero());
+        assert_eq!(contract.balance_of(charlie()), U256::from(770u32));
+    }
+
+    #[ink::test]
+    fn expired_allowance_can_be_reclaimed() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        test::set_block_timestamp::<DefaultEnvironment>(100);
+        contract.approve_with_expiry(bob, U256::from(50u32), 200).unwrap();
+        assert_eq!(contract.allowance(alice, bob), U256::from(50u32));
+        assert_eq!(contract.reclaim_stale_allowance(alice, bob), Err(Error::AllowanceNotStale));
+
+        test::set_block_timestamp::<DefaultEnvironment>(200);
+        assert_eq!(contract.allowance(alice, bob), U256::zero());
+        let initial_events_len = test::recorded_events().len();
+        contract.reclaim_stale_allowance(alice, bob).unwrap();
+        assert_eq!(test::recorded_events().len(), initial_events_len + 1);
+        assert_eq!(contract.allowance_expiry(alice, bob), None);
+        assert_eq!(contract.total_granted_to(bob), U256::zero());
+    }
+
+    #[ink::test]
+    fn reclaim_requires_an_expiry() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(50u32)).unwrap();
+        assert_eq!(contract.reclaim_stale_allowance(alice, bob), Err(Error::AllowanceNotStale));
+        test::set_caller(bob);
+        assert_eq!(contract.reclaim_stale_allowance(alice, bob), Err(Error::NotOwner));
+    }
+
+    #[ink::test]
+    fn loyalty_multiplier_grows_with_holding_time() {
+        let (_alice, bob) = setup();
+        let day = 24 * 60 * 60;
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_loyalty_params(100, 500).unwrap();
+        assert_eq!(contract.first_seen(bob), None);
+
+        test::set_block_timestamp::<DefaultEnvironment>(day);
+        contract.transfer(bob, U256::from(10u32)).unwrap();
+        assert_eq!(contract.first_seen(bob), Some(day));
+        test::set_block_timestamp::<DefaultEnvironment>(2 * day);
+        contract.transfer(bob, U256::from(10u32)).unwrap();
+        assert_eq!(contract.first_seen(bob), Some(day));
+
+        assert_eq!(contract.loyalty_multiplier(bob), 100);
+        test::set_block_timestamp::<DefaultEnvironment>(4 * day + 1);
+        assert_eq!(contract.holding_duration(bob), 3 * day + 1);
+        assert_eq!(contract.loyalty_multiplier(bob), 300);
+        test::set_block_timestamp::<DefaultEnvironment>(30 * day);
+        assert_eq!(contract.loyalty_multiplier(bob), 500);
+    }
+
+    #[ink::test]
+    fn quote_transfer_applies_exemptions_and_holidays() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_fee(500).unwrap();
+        let value = U256::from(200u32);
+        assert_eq!(contract.quote_transfer(bob, charlie(), value), (U256::from(190u32), U256::from(10u32)));
+
+        contract.set_fee_exempt(bob, true).unwrap();
+        assert_eq!(contract.quote_transfer(bob, charlie(), value), (value, U256::zero()));
+        contract.set_fee_exempt(bob, false).unwrap();
+
+        contract.set_fee_holiday(100, 200).unwrap();
+        test::set_block_timestamp::<DefaultEnvironment>(150);
+        assert_eq!(contract.quote_transfer(bob, charlie(), value), (value, U256::zero()));
+    }
+
+    #[ink::test]
+    fn fee_is_split_between_two_collectors() {
+        let (alice, bob) = setup();
+        let treasury: Address = [3u8; 20].into();
+        let burn: Address = [4u8; 20].into();
+        let mut contract = Erc20::new(U256::from(100_000u32));
+        contract.transfer(bob, U256::from(10_000u32)).unwrap();
+        contract.set_fee(1000).unwrap();
+        contract.set_fee_split(treasury, burn, 7000).unwrap();
+
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(treasury), U256::from(70u32));
+        assert_eq!(contract.balance_of(burn), U256::from(30u32));
+
+        // A fee of 3 splits 2.1/0.9, with the rounding remainder going to the first collector.
+        contract.transfer(charlie(), U256::from(30u32)).unwrap();
+        assert_eq!(contract.balance_of(treasury), U256::from(73u32));
+        assert_eq!(contract.balance_of(burn), U256::from(30u32));
+        assert_eq!(contract.balance_of(charlie()), U256::from(927u32));
+
+        test::set_caller(alice);
+        contract.clear_fee_split().unwrap();
+        assert_eq!(contract.fee_split(), None);
+    }
+
+    #[ink::test]
+    fn is_owner_checks_caller() {
+        let (_alice, bob) = setup();
+        let contract = Erc20::new(U256::from(1000u32));
+        assert!(contract.is_owner());
+        test::set_caller(bob);
+        assert!(!contract.is_owner());
+    }
+
+    #[ink::test]
+    fn whitelist_entries_expire() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_whitelist_mode(true).unwrap();
+        contract.whitelist_until(bob, 100).unwrap();
+        contract.whitelist_until(charlie(), 200).unwrap();
+        contract.transfer(bob, U256::from(50u32)).unwrap();
+
+        test::set_caller(bob);
+        test::set_block_timestamp::<DefaultEnvironment>(100);
+        contract.transfer(charlie(), U256::from(10u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(10u32));
+
+        test::set_block_timestamp::<DefaultEnvironment>(101);
+        assert!(!contract.is_whitelisted(bob));
+        assert_eq!(contract.transfer(charlie(), U256::from(10u32)), Err(Error::NotWhitelisted));
+    }
+
+    #[ink::test]
+    fn total_held_by_sums_balances() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(100u32)).unwrap();
+        contract.transfer(charlie(), U256::from(50u32)).unwrap();
+        assert_eq!(contract.total_held_by(vec![alice, bob, charlie()]), Ok(U256::from(1000u32)));
+        assert_eq!(contract.total_held_by(vec![bob, charlie()]), Ok(U256::from(150u32)));
+    }
+
+    #[ink::test]
+    fn approve_and_distribute_sets_each_allowance() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve_and_distribute(vec![bob, charlie()], vec![U256::from(600u32), U256::from(400u32)]).unwrap();
+        assert_eq!(contract.allowance(alice, bob), U256::from(600u32));
+        assert_eq!(contract.allowance(alice, charlie()), U256::from(400u32));
+    }
+
+    #[ink::test]
+    fn approve_and_distribute_clears_single_use_flags() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve_once(bob, U256::from(100u32)).unwrap();
+        contract.approve_and_distribute(vec![bob], vec![U256::from(300u32)]).unwrap();
+        assert!(!contract.is_single_use(alice, bob));
+
+        test::set_caller(bob);
+        contract.transfer_from(alice, bob, U256::from(100u32)).unwrap();
+        assert_eq!(contract.allowance(alice, bob), U256::from(200u32));
+    }
+
+    #[ink::test]
+    fn approve_and_distribute_rejects_over_approval() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(
+            contract.approve_and_distribute(vec![bob, charlie()], vec![U256::from(600u32), U256::from(401u32)]),
+            Err(Error::ApprovalExceedsBalance)
+        );
+        assert_eq!(contract.approve_and_distribute(vec![bob], vec![]), Err(Error::LengthMismatch));
+        assert_eq!(contract.allowance(alice, bob), U256::zero());
+    }
+
+    #[ink::test]
+    fn supply_share_bps_reports_concentration() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(250u32)).unwrap();
+        assert_eq!(contract.supply_share_bps(bob), 2500);
+        assert_eq!(contract.supply_share_bps(alice), 7500);
+        assert_eq!(contract.supply_share_bps(charlie()), 0);
+        assert_eq!(Erc20::new(U256::zero()).supply_share_bps(alice), 0);
+    }
+
+    #[ink::test]
+    fn frozen_account_can_receive_but_not_send() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.freeze_account(bob).unwrap();
+        contract.transfer(bob, U256::from(100u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(100u32));
+
+        test::set_caller(bob);
+        assert_eq!(contract.transfer(charlie(), U256::from(10u32)), Err(Error::AccountFrozen));
+        assert_eq!(contract.freeze_account(alice), Err(Error::NotOwner));
+
+        test::set_caller(alice);
+        contract.unfreeze_account(bob).unwrap();
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(10u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(10u32));
+    }
+
+    #[ink::test]
+    fn snapshot_pairs_matches_individual_reads() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(100u32)).unwrap();
+        contract.approve(bob, U256::from(30u32)).unwrap();
+        test::set_caller(bob);
+        contract.approve(charlie(), U256::from(20u32)).unwrap();
+
+        let pairs = vec![(alice, bob), (bob, charlie()), (charlie(), alice)];
+        let expected: Vec<(U256, U256)> = pairs.iter().map(|(owner, spender)| (contract.balance_of(*owner), contract.allowance(*owner, *spender))).collect();
+        assert_eq!(contract.snapshot_pairs(pairs), expected);
+        assert_eq!(expected, vec![(U256::from(900u32), U256::from(30u32)), (U256::from(100u32), U256::from(20u32)), (U256::zero(), U256::zero())]);
+    }
+
+    #[ink::test]
+    fn excluded_account_does_not_accrue_reflections() {
+        let (alice, bob) = setup();
+        let dave: Address = [3u8; 20].into();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(3000u32)).unwrap();
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        contract.transfer(dave, U256::from(1000u32)).unwrap();
+        contract.set_reflection_bps(1000).unwrap();
+        contract.exclude_from_reflection(bob).unwrap();
+        assert!(contract.is_excluded_from_reflection(bob));
+
+        // All 100 reflected tokens go to charlie, the only eligible holder.
+        test::set_caller(dave);
+        contract.transfer(alice, U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(3000u32));
+        assert_eq!(contract.balance_of(charlie()), U256::from(1100u32));
+
+        test::set_caller(alice);
+        contract.include_in_reflection(bob).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(3000u32));
+        test::set_caller(charlie());
+        contract.transfer(alice, U256::from(1000u32)).unwrap();
+        // 100 is now shared across bob's 3000 and charlie's remaining 100.
+        assert_eq!(contract.balance_of(bob), U256::from(3096u32));
+        assert_eq!(contract.balance_of(charlie()), U256::from(103u32));
+    }
+
+    #[ink::test]
+    fn burn_to_target_burns_from_treasury() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(300u32)).unwrap();
+        contract.set_treasury(bob).unwrap();
+
+        contract.burn_to_target(U256::from(800u32)).unwrap();
+        assert_eq!(contract.total_supply(), U256::from(800u32));
+        assert_eq!(contract.balance_of(bob), U256::from(100u32));
+
+        assert_eq!(contract.burn_to_target(U256::from(801u32)), Err(Error::TargetAboveSupply));
+        assert_eq!(contract.burn_to_target(U256::from(600u32)), Err(Error::InsufficientBalance));
+        assert_eq!(contract.total_supply(), U256::from(800u32));
+    }
+
+    #[ink::test]
+    fn fee_tiers_scale_with_transfer_size() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1_000_000u32));
+        contract.transfer(bob, U256::from(100_000u32)).unwrap();
+        contract.set_fee_tiers(vec![(U256::zero(), 100), (U256::from(1000u32), 200), (U256::from(10_000u32), 500)]).unwrap();
+
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(500u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(495u32));
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(1475u32));
+        contract.transfer(charlie(), U256::from(20_000u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(20_475u32));
+    }
+
+    #[ink::test]
+    fn fee_tiers_must_increase() {
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.set_fee_tiers(vec![(U256::from(10u32), 100), (U256::from(10u32), 200)]), Err(Error::InvalidTiers));
+        assert_eq!(contract.set_fee_tiers(vec![(U256::from(10u32), 100), (U256::from(5u32), 200)]), Err(Error::InvalidTiers));
+        assert_eq!(contract.set_fee_tiers(vec![(U256::zero(), 10_001)]), Err(Error::InvalidFee));
+        assert!(contract.fee_tiers().is_empty());
+    }
+
+    #[ink::test]
+    fn sequenced_transfers_carry_increasing_seq() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        for _ in 0..3 {
+            contract.transfer(bob, U256::from(10u32)).unwrap();
+        }
+        let _ = contract.transfer(bob, U256::from(10_000u32));
+        assert_eq!(contract.transfer_seq(), 3);
+
+        let (_, sequenced_topic) = event_topics().into_iter().find(|(name, _)| name == "SequencedTransfer").unwrap();
+        let seqs: Vec<u64> = test::recorded_events()
+            .into_iter()
+            .filter(|event| event.topics[0].as_slice() == &sequenced_topic[..])
+            .map(|event| {
+                let mut data = &event.data[..];
+                let seq: u64 = Decode::decode(&mut data).unwrap();
+                let from: Address = Decode::decode(&mut data).unwrap();
+                assert_eq!(from, alice);
+                seq
+            })
+            .collect();
+        assert_eq!(seqs, vec![1, 2, 3]);
+    }
+
+    fn policy(max_per_tx: u32, max_total: u32, expires_at: u64) -> SpendPolicy {
+        SpendPolicy { max_per_tx: U256::from(max_per_tx), max_total: U256::from(max_total), expires_at }
+    }
+
+    #[ink::test]
+    fn spend_policy_allows_compliant_transfer_from() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(500u32)).unwrap();
+        contract.set_spend_policy(bob, policy(100, 300, 1000)).unwrap();
+
+        test::set_caller(bob);
+        contract.transfer_from(alice, bob, U256::from(100u32)).unwrap();
+        assert_eq!(contract.policy_spent(alice, bob), U256::from(100u32));
+        assert_eq!(contract.balance_of(bob), U256::from(100u32));
+    }
+
+    #[ink::test]
+    fn spend_policy_enforces_per_tx_limit() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(500u32)).unwrap();
+        contract.set_spend_policy(bob, policy(100, 0, 0)).unwrap();
+        test::set_caller(bob);
+        assert_eq!(contract.transfer_from(alice, bob, U256::from(101u32)), Err(Error::PolicyPerTxExceeded));
+    }
+
+    #[ink::test]
+    fn spend_policy_enforces_total_limit() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(500u32)).unwrap();
+        contract.set_spend_policy(bob, policy(0, 150, 0)).unwrap();
+        test::set_caller(bob);
+        contract.transfer_from(alice, bob, U256::from(100u32)).unwrap();
+        assert_eq!(contract.transfer_from(alice, bob, U256::from(51u32)), Err(Error::PolicyTotalExceeded));
+    }
+
+    #[ink::test]
+    fn spend_policy_enforces_expiry() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(500u32)).unwrap();
+        contract.set_spend_policy(bob, policy(0, 0, 100)).unwrap();
+        test::set_caller(bob);
+        test::set_block_timestamp::<DefaultEnvironment>(100);
+        assert_eq!(contract.transfer_from(alice, bob, U256::from(1u32)), Err(Error::PolicyExpired));
+    }
+
+    #[ink::test]
+    fn recent_activity_keeps_last_ten_transfers() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        for i in 1..=12u32 {
+            test::set_block_timestamp::<DefaultEnvironment>(u64::from(i));
+            contract.transfer(bob, U256::from(i)).unwrap();
+        }
+
+        let expected: Vec<(Address, U256, u64)> = (3..=12u32).map(|i| (bob, U256::from(i), u64::from(i))).collect();
+        assert_eq!(contract.recent_activity(alice), expected);
+        let received = contract.recent_activity(bob);
+        assert_eq!(received.len(), 10);
+        assert_eq!(received[9], (alice, U256::from(12u32), 12));
+        assert!(contract.recent_activity(charlie()).is_empty());
+    }
+
+    #[ink::test]
+    fn fees_accumulate_pending_liquidity() {
+        let (alice, bob) = setup();
+        let pool: Address = [3u8; 20].into();
+        let mut contract = Erc20::new(U256::from(100_000u32));
+        contract.transfer(bob, U256::from(10_000u32)).unwrap();
+        contract.set_fee(1000).unwrap();
+        contract.set_liquidity_config(5000, pool).unwrap();
+        assert_eq!(contract.add_liquidity(), Ok(()));
+
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        contract.transfer(charlie(), U256::from(2000u32)).unwrap();
+        assert_eq!(contract.pending_liquidity(), U256::from(150u32));
+        assert_eq!(contract.balance_of(charlie()), U256::from(2700u32));
+        assert_eq!(contract.balance_of(alice), U256::from(90_150u32));
+        assert_eq!(contract.add_liquidity(), Err(Error::NotOwner));
+
+        test::set_caller(alice);
+        assert_eq!(contract.redenominate(10, true), Err(Error::EscrowOutstanding));
+        contract.add_liquidity().unwrap();
+        assert_eq!(contract.balance_of(pool), U256::from(150u32));
+        assert_eq!(contract.pending_liquidity(), U256::zero());
+        contract.redenominate(10, true).unwrap();
+    }
+
+    #[ink::test]
+    fn invariant_holds_across_transfers_mints_and_burns() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_fee(100).unwrap();
+        contract.transfer(bob, U256::from(300u32)).unwrap();
+        contract.mint(charlie(), U256::from(50u32)).unwrap();
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(120u32)).unwrap();
+        contract.burn(U256::from(40u32)).unwrap();
+
+        assert!(contract.check_invariant(vec![alice, bob, charlie()]));
+        assert!(!contract.check_invariant(vec![alice, bob]));
+    }
+
+    #[ink::test]
+    fn single_use_approval_consumed_in_full() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve_once(bob, U256::from(100u32)).unwrap();
+        assert!(contract.is_single_use(alice, bob));
+
+        test::set_caller(bob);
+        contract.transfer_from(alice, bob, U256::from(100u32)).unwrap();
+        assert_eq!(contract.allowance(alice, bob), U256::zero());
+        assert!(!contract.is_single_use(alice, bob));
+    }
+
+    #[ink::test]
+    fn single_use_approval_partial_spend_zeroes_remainder() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve_once(bob, U256::from(100u32)).unwrap();
+
+        test::set_caller(bob);
+        contract.transfer_from(alice, bob, U256::from(30u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(30u32));
+        assert_eq!(contract.allowance(alice, bob), U256::zero());
+        assert_eq!(contract.transfer_from(alice, bob, U256::from(1u32)), Err(Error::InsufficientAllowance));
+    }
+
+    #[ink::test]
+    fn token_id_is_deterministic_per_metadata() {
+        let token = Erc20::with_metadata(U256::from(1000u32), String::from("Polka"), String::from("PKT"), 18);
+        let same = Erc20::with_metadata(U256::from(5u32), String::from("Polka"), String::from("PKT"), 6);
+        let other = Erc20::with_metadata(U256::from(1000u32), String::from("Polka"), String::from("PKX"), 18);
+        assert_eq!(token.token_id(), token.token_id());
+        assert_eq!(token.token_id(), same.token_id());
+        assert_ne!(token.token_id(), other.token_id());
+    }
+
+    #[ink::test]
+    fn votes_now_and_at_reads_current_and_past_votes() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.delegate(bob).unwrap();
+        let delegated_at = ink::env::block_number::<DefaultEnvironment>();
+        assert_eq!(contract.get_votes(bob), U256::from(1000u32));
+
+        test::advance_block::<DefaultEnvironment>();
+        contract.transfer(charlie(), U256::from(300u32)).unwrap();
+        let transferred_at = ink::env::block_number::<DefaultEnvironment>();
+        test::advance_block::<DefaultEnvironment>();
+
+        assert_eq!(contract.votes_now_and_at(bob, delegated_at), (U256::from(700u32), U256::from(1000u32)));
+        assert_eq!(contract.votes_now_and_at(bob, transferred_at), (U256::from(700u32), U256::from(700u32)));
+        assert_eq!(contract.votes_now_and_at(alice, transferred_at), (U256::zero(), U256::zero()));
+
+        contract.delegate(alice).unwrap();
+        assert_eq!(contract.get_votes(bob), U256::zero());
+        assert_eq!(contract.get_votes(alice), U256::from(700u32));
+        assert_eq!(contract.get_past_votes(bob, transferred_at), U256::from(700u32));
+    }
+
+    #[ink::test]
+    fn hold_period_blocks_early_transfers() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_min_hold_secs(100).unwrap();
+        test::set_block_timestamp::<DefaultEnvironment>(1000);
+        contract.transfer(bob, U256::from(50u32)).unwrap();
+        assert_eq!(contract.acquired_at(bob), Some(1000));
+
+        test::set_caller(bob);
+        test::set_block_timestamp::<DefaultEnvironment>(1099);
+        assert_eq!(contract.transfer(charlie(), U256::from(10u32)), Err(Error::HoldPeriodActive));
+        test::set_block_timestamp::<DefaultEnvironment>(1100);
+        contract.transfer(charlie(), U256::from(10u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(10u32));
+    }
+
+    #[ink::test]
+    fn batch_set_blacklist_applies_each_entry() {
+        let (_alice, bob) = setup();
+        let dave: Address = [3u8; 20].into();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.batch_set_blacklist(vec![bob, charlie()], vec![true, true]).unwrap();
+        let initial_events_len = test::recorded_events().len();
+        contract.batch_set_blacklist(vec![charlie(), dave], vec![false, true]).unwrap();
+        assert_eq!(test::recorded_events().len(), initial_events_len + 2);
+
+        assert!(contract.is_blacklisted(bob));
+        assert!(!contract.is_blacklisted(charlie()));
+        assert!(contract.is_blacklisted(dave));
+        assert_eq!(contract.transfer(bob, U256::from(1u32)), Err(Error::Blacklisted));
+        contract.transfer(charlie(), U256::from(1u32)).unwrap();
+    }
+
+    #[ink::test]
+    fn batch_set_blacklist_rejects_length_mismatch() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.batch_set_blacklist(vec![bob, charlie()], vec![true]), Err(Error::LengthMismatch));
+        assert!(!contract.is_blacklisted(bob));
+    }
+
+    #[ink::test]
+    fn can_transfer_reports_restrictions() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.can_transfer(bob, U256::from(1000u32)), Ok(()));
+        assert_eq!(contract.can_transfer(bob, U256::from(1001u32)), Err(Error::InsufficientBalance));
+
+        contract.set_blacklisted(bob, true).unwrap();
+        assert_eq!(contract.can_transfer(bob, U256::from(1u32)), Err(Error::Blacklisted));
+        assert_eq!(contract.can_transfer(charlie(), U256::from(1u32)), Ok(()));
+
+        contract.set_transfers_paused(true).unwrap();
+        assert_eq!(contract.can_transfer(charlie(), U256::from(1u32)), Err(Error::TransfersPaused));
+        assert_eq!(contract.balance_of(alice), U256::from(1000u32));
+    }
+
+    #[ink::test]
+    fn migration_seeds_balances_until_finalized() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::for_migration(String::from("Polka"), String::from("PKT"), 18);
+        assert!(contract.migration_mode());
+        contract.seed_balance(alice, U256::from(500u32)).unwrap();
+        contract.seed_balance(bob, U256::from(300u32)).unwrap();
+        contract.seed_balance(charlie(), U256::from(200u32)).unwrap();
+        assert_eq!(contract.total_supply(), U256::from(1000u32));
+        assert_eq!(contract.balance_of(bob), U256::from(300u32));
+
+        test::set_caller(bob);
+        assert_eq!(contract.seed_balance(bob, U256::from(1u32)), Err(Error::NotOwner));
+        test::set_caller(alice);
+        contract.finalize_migration().unwrap();
+        assert_eq!(contract.seed_balance(bob, U256::from(1u32)), Err(Error::MigrationClosed));
+        assert_eq!(contract.total_supply(), U256::from(1000u32));
+    }
+
+    #[ink::test]
+    fn regular_deployments_cannot_seed() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.seed_balance(bob, U256::from(1u32)), Err(Error::MigrationClosed));
+    }
+
+    #[ink::test]
+    fn referrer_receives_minted_bonus() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(5000u32)).unwrap();
+        assert_eq!(contract.set_referral_bps(501), Err(Error::InvalidFee));
+        contract.set_referral_bps(100).unwrap();
+
+        test::set_caller(bob);
+        contract.set_referrer(alice).unwrap();
+        assert_eq!(contract.set_referrer(charlie()), Err(Error::ReferrerAlreadySet));
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(alice), U256::from(5010u32));
+        assert_eq!(contract.total_supply(), U256::from(10_010u32));
+    }
+
+    #[ink::test]
+    fn referral_bonus_skips_self_transfers_and_payments_to_referrer() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(5000u32)).unwrap();
+        contract.set_referral_bps(100).unwrap();
+
+        test::set_caller(bob);
+        contract.set_referrer(charlie()).unwrap();
+        contract.transfer(bob, U256::from(1000u32)).unwrap();
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(1000u32));
+        assert_eq!(contract.total_supply(), U256::from(10_000u32));
+    }
+
+    #[ink::test]
+    fn referral_bonus_is_clamped_at_the_cap() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(5000u32)).unwrap();
+        contract.set_referral_bps(100).unwrap();
+        contract.set_cap(Some(U256::from(10_005u32))).unwrap();
+
+        test::set_caller(bob);
+        contract.set_referrer(alice).unwrap();
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        assert_eq!(contract.total_supply(), U256::from(10_005u32));
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(2000u32));
+        assert_eq!(contract.balance_of(alice), U256::from(5005u32));
+        assert_eq!(contract.total_supply(), U256::from(10_005u32));
+    }
+
+    #[ink::test]
+    fn self_referral_is_rejected() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        test::set_caller(bob);
+        assert_eq!(contract.set_referrer(bob), Err(Error::SelfReferral));
+        assert_eq!(contract.referrer_of(bob), None);
+    }
+
+    #[ink::test]
+    fn allowances_round_trip_through_export_and_import() {
+        let (alice, bob) = setup();
+        let dave: Address = [3u8; 20].into();
+        let mut source = Erc20::new(U256::from(1000u32));
+        source.approve(bob, U256::from(10u32)).unwrap();
+        source.approve(charlie(), U256::from(20u32)).unwrap();
+        source.approve(dave, U256::from(30u32)).unwrap();
+        source.approve(dave, U256::zero()).unwrap();
+        let exported = source.export_allowances(alice);
+        assert_eq!(exported, vec![(bob, U256::from(10u32)), (charlie(), U256::from(20u32))]);
+
+        let mut fresh = Erc20::new(U256::from(1000u32));
+        fresh.import_allowances(alice, exported.clone()).unwrap();
+        assert_eq!(fresh.export_allowances(alice), exported);
+        assert_eq!(fresh.allowance(alice, charlie()), U256::from(20u32));
+
+        test::set_caller(bob);
+        assert_eq!(fresh.import_allowances(alice, exported), Err(Error::NotOwner));
+    }
+
+    #[ink::test]
+    fn transfer_with_deadline_rejects_stale_calls() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        test::set_block_timestamp::<DefaultEnvironment>(100);
+        contract.transfer_with_deadline(bob, U256::from(10u32), 100).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(10u32));
+
+        test::set_block_timestamp::<DefaultEnvironment>(101);
+        assert_eq!(contract.transfer_with_deadline(bob, U256::from(10u32), 100), Err(Error::DeadlinePassed));
+        assert_eq!(contract.balance_of(bob), U256::from(10u32));
+    }
+
+    #[ink::test]
+    fn remaining_mintable_tracks_cap_headroom() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.remaining_mintable(), U256::MAX);
+        assert_eq!(contract.set_cap(Some(U256::from(999u32))), Err(Error::CapExceeded));
+
+        contract.set_cap(Some(U256::from(1500u32))).unwrap();
+        contract.mint(bob, U256::from(200u32)).unwrap();
+        assert_eq!(contract.remaining_mintable(), U256::from(300u32));
+        assert_eq!(contract.mint(bob, U256::from(301u32)), Err(Error::CapExceeded));
+        contract.mint(bob, U256::from(300u32)).unwrap();
+        assert_eq!(contract.remaining_mintable(), U256::zero());
+    }
+
+    #[ink::test]
+    fn emptied_balances_are_removed_from_holder_count() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.holder_count(), 1);
+        contract.transfer(bob, U256::from(100u32)).unwrap();
+        contract.transfer(charlie(), U256::from(50u32)).unwrap();
+        assert_eq!(contract.holder_count(), 3);
+
+        test::set_caller(bob);
+        contract.transfer(alice, U256::from(100u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::zero());
+        assert_eq!(contract.holder_count(), 2);
+
+        test::set_caller(charlie());
+        contract.burn(U256::from(50u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::zero());
+        assert_eq!(contract.holder_count(), 1);
+        assert_eq!(Erc20::new(U256::zero()).holder_count(), 0);
+    }
+
+    #[ink::test]
+    fn mint_proposal_executes_on_second_approval() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_minter(bob, true).unwrap();
+        let id = contract.propose_mint(charlie(), U256::from(500u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::zero());
+
+        test::set_caller(bob);
+        contract.approve_mint(id).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(500u32));
+        assert_eq!(
+            contract.mint_proposal(id),
+            Some(MintProposal { to: charlie(), value: U256::from(500u32), approvals: vec![alice, bob], executed: true })
+        );
+        assert_eq!(contract.approve_mint(id), Err(Error::ProposalExecuted));
+        assert_eq!(contract.approve_mint(id + 1), Err(Error::UnknownProposal));
+    }
+
+    #[ink::test]
+    fn proposer_cannot_approve_own_mint() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        let id = contract.propose_mint(bob, U256::from(500u32)).unwrap();
+        assert_eq!(contract.approve_mint(id), Err(Error::AlreadyApproved));
+        assert_eq!(contract.balance_of(bob), U256::zero());
+
+        test::set_caller(bob);
+        assert_eq!(contract.approve_mint(id), Err(Error::NotMinter));
+        assert_eq!(contract.propose_mint(bob, U256::from(1u32)), Err(Error::NotMinter));
+    }
+
+    #[ink::test]
+    fn effective_decimals_follow_redenomination() {
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.effective_decimals(), 18);
+        contract.redenominate(10, true).unwrap();
+        assert_eq!(contract.effective_decimals(), 19);
+        contract.redenominate(1000, false).unwrap();
+        assert_eq!(contract.effective_decimals(), 16);
+        assert_eq!(contract.decimals(), 18);
+    }
+
+    #[ink::test]
+    fn vesting_releases_nothing_before_cliff() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.create_vesting(bob, U256::from(1000u32), 100, 1000, 250).unwrap();
+        assert_eq!(contract.balance_of(test::callee()), U256::from(1000u32));
+
+        test::set_block_timestamp::<DefaultEnvironment>(349);
+        assert_eq!(contract.releasable(bob, 0), U256::zero());
+        test::set_caller(bob);
+        assert_eq!(contract.release(0), Err(Error::NothingToClaim));
+
+        // Past the cliff the schedule counts from `start`, so a quarter has vested at once.
+        test::set_block_timestamp::<DefaultEnvironment>(350);
+        assert_eq!(contract.releasable(bob, 0), U256::from(250u32));
+        contract.release(0).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(250u32));
+
+        test::set_block_timestamp::<DefaultEnvironment>(1100);
+        assert_eq!(contract.releasable(bob, 0), U256::from(750u32));
+        contract.release(0).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(1000u32));
+        assert_eq!(contract.balance_of(test::callee()), U256::zero());
+    }
+
+    #[ink::test]
+    fn vesting_rejects_invalid_schedules() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        assert_eq!(contract.create_vesting(bob, U256::from(1u32), 0, 0, 0), Err(Error::InvalidWindow));
+        assert_eq!(contract.create_vesting(bob, U256::from(1u32), 0, 10, 11), Err(Error::InvalidWindow));
+    }
+
+    #[ink::test]
+    fn release_all_claims_across_schedules() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.create_vesting(bob, U256::from(1000u32), 0, 1000, 0).unwrap();
+        contract.create_vesting(bob, U256::from(600u32), 200, 300, 0).unwrap();
+        assert_eq!(contract.vesting_schedules(bob).len(), 2);
+
+        test::set_block_timestamp::<DefaultEnvironment>(350);
+        assert_eq!(contract.releasable(bob, 0), U256::from(350u32));
+        assert_eq!(contract.releasable(bob, 1), U256::from(300u32));
+        assert_eq!(contract.total_releasable(bob), U256::from(650u32));
+
+        test::set_caller(bob);
+        contract.release_all().unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(650u32));
+        assert_eq!(contract.total_releasable(bob), U256::zero());
+        assert_eq!(contract.release_all(), Err(Error::NothingToClaim));
+    }
+
+    #[ink::test]
+    fn redenominate_waits_for_vesting_to_complete() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.create_vesting(bob, U256::from(1000u32), 0, 100, 0).unwrap();
+        assert_eq!(contract.redenominate(10, false), Err(Error::EscrowOutstanding));
+
+        test::set_block_timestamp::<DefaultEnvironment>(100);
+        test::set_caller(bob);
+        contract.release(0).unwrap();
+        test::set_caller(alice);
+        contract.redenominate(10, false).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(100u32));
+    }
+
+    #[ink::test]
+    fn balance_proof_verifies_only_true_balance() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(42u32)).unwrap();
+        let unsalted = contract.balance_commitment(bob);
+        contract.set_commitment_salt([7u8; 32]).unwrap();
+        assert_ne!(contract.balance_commitment(bob), unsalted);
+
+        assert!(contract.verify_balance_proof(bob, U256::from(42u32)));
+        assert!(!contract.verify_balance_proof(bob, U256::from(43u32)));
+        assert!(!contract.verify_balance_proof(charlie(), U256::from(42u32)));
+    }
+
+    #[ink::test]
+    fn buyback_and_burn_burns_collected_fees() {
+        let (alice, bob) = setup();
+        let collector: Address = [3u8; 20].into();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.transfer(bob, U256::from(5000u32)).unwrap();
+        contract.set_fee(1000).unwrap();
+        contract.set_fee_collector(collector).unwrap();
+        test::set_caller(bob);
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        contract.transfer(charlie(), U256::from(1000u32)).unwrap();
+        assert_eq!(contract.balance_of(collector), U256::from(200u32));
+
+        test::set_caller(alice);
+        let initial_events_len = test::recorded_events().len();
+        contract.buyback_and_burn(U256::from(150u32)).unwrap();
+        assert_eq!(contract.total_supply(), U256::from(9850u32));
+        assert_eq!(contract.balance_of(collector), U256::from(50u32));
+        let events = test::recorded_events();
+        assert_eq!(events.len(), initial_events_len + 2);
+        let amount: U256 = Decode::decode(&mut &events[events.len() - 1].data[..]).unwrap();
+        assert_eq!(amount, U256::from(150u32));
+        assert_eq!(contract.buyback_and_burn(U256::from(51u32)), Err(Error::InsufficientBalance));
+    }
+
+    #[ink::test]
+    fn spend_destination_restricts_transfer_from() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(500u32)).unwrap();
+        assert_eq!(contract.spend_destination(alice, bob), None);
+        contract.set_spend_destination(bob, charlie()).unwrap();
+        assert_eq!(contract.spend_destination(alice, bob), Some(charlie()));
+
+        test::set_caller(bob);
+        assert_eq!(contract.transfer_from(alice, bob, U256::from(100u32)), Err(Error::DestinationRestricted));
+        contract.transfer_from(alice, charlie(), U256::from(100u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(100u32));
+        assert_eq!(contract.allowance(alice, bob), U256::from(400u32));
+    }
+
+    #[ink::test]
+    fn version_and_predecessor_are_reported() {
+        setup();
+        let contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.version(), VERSION);
+        assert_eq!(contract.predecessor(), None);
+
+        let predecessor: Address = [9u8; 20].into();
+        let successor = Erc20::upgraded_from(U256::from(1000u32), predecessor);
+        assert_eq!(successor.predecessor(), Some(predecessor));
+        assert_eq!(successor.total_supply(), U256::from(1000u32));
+    }
+
+    #[ink::test]
+    fn registry_is_owner_set_and_does_not_block_transfers() {
+        let (_alice, bob) = setup();
+        let registry: Address = [9u8; 20].into();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert_eq!(contract.registry(), None);
+        contract.set_registry(Some(registry)).unwrap();
+        assert_eq!(contract.registry(), Some(registry));
+        contract.transfer(bob, U256::from(100u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(100u32));
+
+        test::set_caller(bob);
+        assert_eq!(contract.set_registry(None), Err(Error::NotOwner));
+    }
+
+    #[ink::test]
+    fn has_allowance_compares_against_current_allowance() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(50u32)).unwrap();
+        assert!(contract.has_allowance(alice, bob, U256::from(50u32)));
+        assert!(!contract.has_allowance(alice, bob, U256::from(51u32)));
+
+        contract.approve(charlie(), U256::MAX).unwrap();
+        assert!(contract.has_allowance(alice, charlie(), U256::MAX));
+    }
+
+    #[ink::test]
+    fn has_allowance_is_false_once_expired() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        test::set_block_timestamp::<DefaultEnvironment>(100);
+        contract.approve_with_expiry(bob, U256::from(50u32), 200).unwrap();
+        assert!(contract.has_allowance(alice, bob, U256::from(10u32)));
+        test::set_block_timestamp::<DefaultEnvironment>(200);
+        assert!(!contract.has_allowance(alice, bob, U256::from(10u32)));
+    }
+
+    #[ink::test]
+    fn distribute_to_holders_is_pro_rata_with_dust_to_largest() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(300u32)).unwrap();
+        contract.transfer(charlie(), U256::from(200u32)).unwrap();
+
+        contract.distribute_to_holders(U256::from(100u32)).unwrap();
+        assert_eq!(contract.balance_of(alice), U256::from(550u32));
+        assert_eq!(contract.balance_of(bob), U256::from(330u32));
+        assert_eq!(contract.balance_of(charlie()), U256::from(220u32));
+
+        contract.distribute_to_holders(U256::from(11u32)).unwrap();
+        assert_eq!(contract.balance_of(alice), U256::from(556u32));
+        assert_eq!(contract.balance_of(bob), U256::from(333u32));
+        assert_eq!(contract.balance_of(charlie()), U256::from(222u32));
+        assert_eq!(contract.total_supply(), U256::from(1111u32));
+    }
+
+    #[ink::test]
+    fn distribute_to_holders_skips_contract_escrow() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(bob, U256::from(300u32)).unwrap();
+        contract.transfer(test::callee(), U256::from(200u32)).unwrap();
+
+        contract.distribute_to_holders(U256::from(80u32)).unwrap();
+        assert_eq!(contract.balance_of(alice), U256::from(550u32));
+        assert_eq!(contract.balance_of(bob), U256::from(330u32));
+        assert_eq!(contract.balance_of(test::callee()), U256::from(200u32));
+    }
+
+    #[ink::test]
+    fn distribute_to_holders_requires_holders() {
+        setup();
+        let mut contract = Erc20::new(U256::zero());
+        assert_eq!(contract.distribute_to_holders(U256::from(100u32)), Err(Error::NoHolders));
+    }
+
+    #[ink::test]
+    fn spend_rate_limit_caps_transfer_from_per_window() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.approve(bob, U256::from(500u32)).unwrap();
+        test::set_block_timestamp::<DefaultEnvironment>(1000);
+        contract.set_spend_rate_limit(bob, U256::from(100u32), 60).unwrap();
+        assert_eq!(contract.spend_rate_limit(bob), Some((U256::from(100u32), 60)));
+
+        test::set_caller(bob);
+        contract.transfer_from(alice, bob, U256::from(60u32)).unwrap();
+        contract.transfer_from(alice, bob, U256::from(40u32)).unwrap();
+        assert_eq!(contract.transfer_from(alice, bob, U256::from(1u32)), Err(Error::SpendRateExceeded));
+
+        test::set_block_timestamp::<DefaultEnvironment>(1060);
+        contract.transfer_from(alice, bob, U256::from(100u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(200u32));
+    }
+
+    #[ink::test]
+    fn restrictions_for_lists_every_active_restriction() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        assert!(contract.restrictions_for(bob).is_empty());
+        contract.set_blacklisted(bob, true).unwrap();
+        contract.freeze_account(bob).unwrap();
+        assert_eq!(contract.restrictions_for(bob), vec![RESTRICTION_BLACKLISTED, RESTRICTION_FROZEN]);
+        assert!(contract.restrictions_for(charlie()).is_empty());
+    }
+
+    #[ink::test]
+    fn deposit_mints_and_withdraw_burns_native_backed_tokens() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        test::set_caller(bob);
+        test::set_value_transferred(U256::from(300u32));
+        test::set_contract_balance(test::callee(), U256::from(300u32));
+        contract.deposit().unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(300u32));
+        assert_eq!(contract.total_supply(), U256::from(1300u32));
+        assert_eq!(contract.deposited_of(bob), U256::from(300u32));
+
+        test::set_value_transferred(U256::zero());
+        contract.withdraw(U256::from(120u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(180u32));
+        assert_eq!(contract.total_supply(), U256::from(1180u32));
+        assert_eq!(contract.wrapped_supply(), U256::from(180u32));
+        assert_eq!(test::get_contract_balance::<DefaultEnvironment>(test::callee()).unwrap(), contract.wrapped_supply());
+    }
+
+    #[ink::test]
+    fn withdraw_beyond_balance_fails() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::zero());
+        test::set_caller(bob);
+        test::set_value_transferred(U256::from(50u32));
+        test::set_contract_balance(test::callee(), U256::from(50u32));
+        contract.deposit().unwrap();
+        assert_eq!(contract.withdraw(U256::from(51u32)), Err(Error::InsufficientBalance));
+        assert_eq!(contract.total_supply(), U256::from(50u32));
+    }
+
+    #[ink::test]
+    fn unbacked_tokens_cannot_withdraw_other_deposits() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        test::set_caller(bob);
+        test::set_value_transferred(U256::from(300u32));
+        test::set_contract_balance(test::callee(), U256::from(300u32));
+        contract.deposit().unwrap();
+
+        test::set_caller(alice);
+        test::set_value_transferred(U256::zero());
+        assert_eq!(contract.withdraw(U256::from(300u32)), Err(Error::ExceedsDeposit));
+        assert_eq!(contract.balance_of(alice), U256::from(1000u32));
+        assert_eq!(contract.wrapped_supply(), U256::from(300u32));
+    }
+
+    #[ink::test]
+    fn request_payment_records_request_and_emits_event() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        let memo = [7u8; 32];
+        test::set_caller(bob);
+        let initial_events_len = test::recorded_events().len();
+        contract.request_payment(alice, U256::from(250u32), memo).unwrap();
+        assert_eq!(contract.payment_request(alice, bob), Some((U256::from(250u32), memo)));
+        assert_eq!(contract.payment_request(bob, alice), None);
+
+        let events = test::recorded_events();
+        assert_eq!(events.len(), initial_events_len + 1);
+        let decoded: (Address, Address, U256, [u8; 32]) = Decode::decode(&mut &events[events.len() - 1].data[..]).unwrap();
+        assert_eq!(decoded, (alice, bob, U256::from(250u32), memo));
+    }
+
+    #[ink::test]
+    fn transfers_to_the_contract_are_tracked_by_default() {
+        setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(test::callee(), U256::from(40u32)).unwrap();
+        assert_eq!(contract.received_by_contract(), U256::from(40u32));
+        assert_eq!(contract.balance_of(test::callee()), U256::from(40u32));
+    }
+
+    #[ink::test]
+    fn rescue_received_is_limited_to_tracked_receipts() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.transfer(test::callee(), U256::from(40u32)).unwrap();
+
+        assert_eq!(contract.rescue_received(bob, U256::from(41u32)), Err(Error::InsufficientBalance));
+        contract.rescue_received(bob, U256::from(30u32)).unwrap();
+        assert_eq!(contract.balance_of(bob), U256::from(30u32));
+        assert_eq!(contract.received_by_contract(), U256::from(10u32));
+
+        test::set_caller(bob);
+        assert_eq!(contract.rescue_received(bob, U256::from(10u32)), Err(Error::NotOwner));
+    }
+
+    #[ink::test]
+    fn transfers_to_the_contract_can_be_rejected() {
+        setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_reject_self_transfers(true).unwrap();
+        assert_eq!(contract.transfer(test::callee(), U256::from(40u32)), Err(Error::SelfContractTransfer));
+        assert_eq!(contract.transfer(charlie(), U256::from(40u32)), Ok(()));
+        assert_eq!(contract.received_by_contract(), U256::zero());
+    }
+
+    #[ink::test]
+    fn cost_basis_is_a_weighted_average_of_receipt_prices() {
+        let (_alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(1000u32));
+        contract.set_last_price(U256::from(10u32)).unwrap();
+        contract.transfer(bob, U256::from(100u32)).unwrap();
+        assert_eq!(contract.cost_basis_of(bob), U256::from(10u32));
+
+        contract.set_last_price(U256::from(40u32)).unwrap();
+        contract.transfer(bob, U256::from(300u32)).unwrap();
+        assert_eq!(contract.cost_basis_of(bob), U256::from(32u32));
+        assert_eq!(contract.cost_basis_of(charlie()), U256::zero());
+    }
+
+    #[ink::test]
+    fn long_term_holders_pay_discounted_fee() {
+        let (alice, bob) = setup();
+        let mut contract = Erc20::new(U256::from(10_000u32));
+        contract.set_fee(1000).unwrap();
+        contract.set_fee_collector(charlie()).unwrap();
+        contract.set_holder_fee_tiers(vec![(30 * SECONDS_PER_DAY, 500), (365 * SECONDS_PER_DAY, 100)]).unwrap();
+        test::set_block_timestamp::<DefaultEnvironment>(1000);
+        contract.transfer(bob, U256::from(1000u32)).unwrap();
+
+        test::set_caller(bob);
+        assert_eq!(contract.effective_fee_bps(bob), 1000);
+        contract.transfer(alice, U256::from(100u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(110u32));
+
+        test::set_block_timestamp::<DefaultEnvironment>(1000 + 30 * SECONDS_PER_DAY);
+        assert_eq!(contract.effective_fee_bps(bob), 500);
+        contract.transfer(alice, U256::from(100u32)).unwrap();
+        assert_eq!(contract.balance_of(charlie()), U256::from(115u32));
+    }
+}
@@ -0,0 +1,59 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Test double for contracts the token calls back into. Every hook records its
+/// arguments, or traps when the receiver was deployed with `fail = true`.
+#[ink::contract]
+pub mod mock_receiver {
+    use ink::U256;
+    use ink::env::call::{ build_call, ExecutionInput, Selector };
+
+    #[ink(storage)]
+    pub struct MockReceiver {
+        fail: bool,
+        last_payment: Option<(Address, U256, u64)>,
+        last_received: Option<(Address, U256)>,
+        last_notification: Option<(Address, Address, Address, U256)>,
+    }
+
+    impl MockReceiver {
+        #[ink(constructor)]
+        pub fn new(fail: bool) -> Self { Self { fail, last_payment: None, last_received: None, last_notification: None } }
+
+        /// Opts this contract into `token`'s receiver registry.
+        #[ink(message)]
+        pub fn register_with(&mut self, token: Address) {
+            build_call::<Environment>()
+                .call(token)
+                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!("register_as_receiver"))))
+                .returns::<()>()
+                .invoke()
+        }
+
+        #[ink(message)]
+        pub fn on_token_received(&mut self, from: Address, value: U256) {
+            assert!(!self.fail, "tokens rejected");
+            self.last_received = Some((from, value));
+        }
+
+        #[ink(message)]
+        pub fn last_received(&self) -> Option<(Address, U256)> { self.last_received }
+
+        #[ink(message)]
+        pub fn on_payment(&mut self, from: Address, value: U256, invoice_id: u64) {
+            assert!(!self.fail, "payment rejected");
+            self.last_payment = Some((from, value, invoice_id));
+        }
+
+        #[ink(message)]
+        pub fn last_payment(&self) -> Option<(Address, U256, u64)> { self.last_payment }
+
+        #[ink(message)]
+        pub fn notify_transfer(&mut self, token: Address, from: Address, to: Address, value: U256) {
+            assert!(!self.fail, "notification rejected");
+            self.last_notification = Some((token, from, to, value));
+        }
+
+        #[ink(message)]
+        pub fn last_notification(&self) -> Option<(Address, Address, Address, U256)> { self.last_notification }
+    }
+}